@@ -16,251 +16,103 @@ if context ~= nil then
 		..reset())
 end
 
--- OS
-if distro ~= nil then
-	print(""
-		..bold()
-		..distroColors[2]
-		.."OS"
-		..reset()
-		..": "
-		..distro.shortname
-		.." "
-		..distro.architecture)
+-- Joins the parts that aren't nil or empty with spaces.
+local function words(...)
+	local parts = {}
+	for _, part in pairs({...}) do
+		if part ~= "" then
+			table.insert(parts, tostring(part))
+		end
+	end
+	return table.concat(parts, " ")
 end
 
--- Host
-if host ~= nil then
-	print(""
-		..bold()
-		..distroColors[2]
-		.."Host"
-		..reset()
-		..": "
-		..host.model)
+local function plural(n, unit)
+	if n ~= 1 and n ~= 0 then
+		return n.." "..unit.."s"
+	end
+	return n.." "..unit
 end
 
--- Kernel
-if kernel ~= nil then
-	print(""
-		..bold()
-		..distroColors[2]
-		.."Kernel"
-		..reset()
-		..": "
-		..kernel.name
-		.." "
-		..kernel.version)
+local function hostModel()
+	if host == nil or host.model == "" then return nil end
+	local model = words(host.model, host.version)
+	if host.virtualization ~= nil then
+		model = model.." ("..host.virtualization..")"
+	end
+	return model
 end
 
--- Uptime
-if uptime ~= nil then
-	local output = ""
-	local function comma()
-		if output ~= "" then output = output..", " end
-	end
-	local function s(v)
-		if v ~= 1 and v ~= 0 then
-			return "s"
-		else
-			return ""
-		end
-	end
+local function uptimeText()
+	if uptime == nil then return nil end
+	local parts = {}
 	if uptime.days >= 1 then
-		output = output..uptime.days.." day"..s(uptime.days)
+		table.insert(parts, plural(uptime.days, "day"))
 	end
 	if uptime.hours >= 1 then
-		comma()
-		output = output..uptime.hours.." hour"..s(uptime.hours)
+		table.insert(parts, plural(uptime.hours, "hour"))
 	end
 	if uptime.minutes >= 1 then
-		comma()
-		output = output..uptime.minutes.." minute"..s(uptime.minutes)
+		table.insert(parts, plural(uptime.minutes, "minute"))
 	elseif uptime.hours == 0 then
-		comma()
-		output = output..uptime.seconds.." second"..s(uptime.seconds)
+		table.insert(parts, plural(uptime.seconds, "second"))
 	end
-
-	print(""
-		..bold()
-		..distroColors[2]
-		.."Uptime"
-		..reset()
-		..": "
-		..output)
+	return table.concat(parts, ", ")
 end
 
--- Packages
-if packageManagers ~= nil then
-	local output = ""
-	if #packageManagers ~= 0 then
-		for i,packageManager in pairs(packageManagers) do
-			if packageManager.packages == 0 then
-				table.remove(packageManagers, i)
-			end
+local function packages()
+	if packageManagers == nil then return nil end
+	local parts = {}
+	for _, packageManager in ipairs(packageManagers) do
+		if packageManager.packages ~= 0 then
+			table.insert(parts, packageManager.packages.." ("..packageManager.name..")")
 		end
-		for i,packageManager in pairs(packageManagers) do
-			if i ~= #packageManagers then
-				output = output
-					..packageManager.packages
-					.." ("
-					..packageManager.name
-					.."), "
-			else
-				output = output
-					..packageManager.packages
-					.." ("
-					..packageManager.name
-					..")"
-			end
-		end
-	else
-		output = "0"
 	end
-	print(""
-		..bold()
-		..distroColors[2]
-		.."Packages"
-		..reset()
-		..": "
-		..output)
+	if #parts == 0 then return "0" end
+	return table.concat(parts, ", ")
 end
 
--- Shell
-if shell ~= nil then
-	print(""
-		..bold()
-		..distroColors[2]
-		.."Shell"
-		..reset()
-		..": "
-		..shell.name
-		.." "
-		..shell.version)
-end
-
--- Resolution
-if resolution ~= nil then
+local function resolutionText()
+	if resolution == nil then return nil end
+	local size = resolution.width.."x"..resolution.height
 	if resolution.refresh ~= nil then
-		print(""
-			..bold()
-			..distroColors[2]
-			.."Resolution"
-			..reset()
-			..": "
-			..resolution.width
-			.."x"
-			..resolution.height
-			.." @ "
-			..round(resolution.refresh)
-			.."Hz")
-	else
-		print(""
-			..bold()
-			..distroColors[2]
-			.."Resolution"
-			..reset()
-			..": "
-			..resolution.width
-			.."x"
-			..resolution.height)
+		return size.." @ "..round(resolution.refresh).."Hz"
 	end
+	return size
 end
 
--- DE
-if de ~= nil then
-	print(""
-		..bold()
-		..distroColors[2]
-		.."DE"
-		..reset()
-		..": "
-		..de.name
-		.." "
-		..de.version)
-end
-
--- WM
-if wm ~= nil then
-	print(""
-		..bold()
-		..distroColors[2]
-		.."WM"
-		..reset()
-		..": "
-		..wm)
-end
-
--- CPU
-if cpu ~= nil then
+local function cpuText()
+	if cpu == nil then return nil end
 	local freq = (cpu.freq >= 1000)
 		and ""..(cpu.freq / 1000).."GHz"
 		or  ""..cpu.freq.."MHz"
-	print(""
-		..bold()
-		..distroColors[2]
-		.."CPU"
-		..reset()
-		..": "
-		..cpu.name
-		.." ("
-		..cpu.cores
-		..") @ "
-		..freq)
+	return cpu.name.." ("..cpu.cores..") @ "..freq
 end
 
--- GPU
-if gpus ~= nil then
-	if #gpus ~= 1 then
-		print(""
-			..bold()
-			..distroColors[2]
-			.."GPUs"
-			..reset()
-			..": ")
-		for _,gpu in pairs(gpus) do
-			print(" - "..gpu.brand.." "..gpu.name)
-		end
-	else
-		print(""
-			..bold()
-			..distroColors[2]
-			.."GPU"
-			..reset()
-			..": "
-			..gpus[1].brand
-			.." "
-			..gpus[1].name)
+local function gpuList()
+	if gpus == nil then return nil end
+	local list = {}
+	for _, gpu in ipairs(gpus) do
+		table.insert(list, words(gpu.brand, gpu.name))
 	end
+	return list
 end
 
--- Motherboard
-if motherboard ~= nil then
-	print(""
-		..bold()
-		..distroColors[2]
-		.."Board"
-		..reset()
-		..": "
-		..motherboard.vendor
-		.." "
-		..motherboard.name)
-end
-
--- Memory
-if memory ~= nil then
-	-- This memory math is probably inaccurate, but idk how to make it right ;-;
-	print(""
-		..bold()
-		..distroColors[2]
-		.."Memory"
-		..reset()
-		..": "
-		..math.floor(memory.used / 1024)
-		.."MB / "
-		..math.floor(memory.max / 1024)
-		.."MB")
-end
+-- line() leaves out anything that's nil or empty.
+line("OS", distro and words(distro.shortname, distro.architecture))
+line("Host", hostModel())
+line("Kernel", kernel and words(kernel.name, kernel.version))
+line("Uptime", uptimeText())
+line("Packages", packages())
+line("Shell", shell and words(shell.name, shell.version))
+line("Resolution", resolutionText())
+line("DE", de and words(de.name, de.version))
+line("WM", wm)
+line("CPU", cpuText())
+line(gpus ~= nil and #gpus > 1 and "GPUs" or "GPU", gpuList())
+line("Board", motherboard and words(motherboard.vendor, motherboard.name))
+-- This memory math is probably inaccurate, but idk how to make it right ;-;
+line("Memory", memory and math.floor(memory.used / 1024).."MB / "..math.floor(memory.max / 1024).."MB")
 
 -- Palette
 print("")
@@ -0,0 +1,492 @@
+use crate::base64;
+use crate::image;
+
+use crate::color::{Color, ColorSupport};
+use crate::misc::Terminal;
+
+use std::collections::BTreeMap;
+use std::env;
+use std::io::Cursor;
+use std::path::Path;
+
+use base64::Engine;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageOutputFormat, RgbaImage};
+
+/// Used when the terminal doesn't report its size in pixels.
+const FALLBACK_CELL: (u32, u32) = (8, 16);
+
+/// Kitty wants the payload split into chunks of at most this many bytes.
+const KITTY_CHUNK: usize = 4096;
+
+/// Characters used by the ASCII backend, from darkest to brightest.
+const RAMP: &[u8] = b" .:-=+*#%@";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Backend {
+    Kitty,
+    Sixel,
+    Iterm,
+    HalfBlock,
+    Braille,
+    Ascii,
+}
+
+fn fg(support: ColorSupport, c: [u8; 3]) -> String {
+    Color::Rgb(c[0], c[1], c[2]).fg(support)
+}
+
+fn bg(support: ColorSupport, c: [u8; 3]) -> String {
+    Color::Rgb(c[0], c[1], c[2]).bg(support)
+}
+
+/// Everything that controls how an image gets rendered.
+pub(crate) struct Options {
+    pub backend: Backend,
+    /// How the text backends color their output.
+    pub colors: ColorSupport,
+    /// Width in columns. When unset, the image is fit to the terminal.
+    pub width: Option<u32>,
+}
+
+impl Backend {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "kitty" => Some(Backend::Kitty),
+            "sixel" => Some(Backend::Sixel),
+            "iterm" => Some(Backend::Iterm),
+            "halfblock" => Some(Backend::HalfBlock),
+            "braille" => Some(Backend::Braille),
+            "ascii" => Some(Backend::Ascii),
+            _ => None,
+        }
+    }
+
+    /// Guesses the best backend from the environment. Falls back to
+    /// half-blocks, which work anywhere with truecolor support.
+    pub fn detect() -> Self {
+        let term = env::var("TERM").unwrap_or_default();
+        let term_program = env::var("TERM_PROGRAM").unwrap_or_default();
+
+        if env::var("KITTY_WINDOW_ID").is_ok()
+            || term == "xterm-kitty"
+            || term == "xterm-ghostty"
+            || term_program == "ghostty"
+        {
+            Backend::Kitty
+        } else if term_program == "iTerm.app" || term_program == "WezTerm" {
+            Backend::Iterm
+        } else if term.contains("foot")
+            || term.contains("mlterm")
+            || term.contains("yaft")
+            || term.contains("sixel")
+        {
+            Backend::Sixel
+        } else {
+            Backend::HalfBlock
+        }
+    }
+
+    /// Whether this backend draws real pixels rather than text.
+    fn is_graphics(&self) -> bool {
+        matches!(self, Backend::Kitty | Backend::Sixel | Backend::Iterm)
+    }
+}
+
+/// An image rendered for the terminal, along with the number of cells it
+/// covers.
+pub(crate) struct Rendered {
+    pub inner: String,
+    pub width: i32,
+    pub height: i32,
+}
+
+pub(crate) fn render(
+    path: &Path,
+    options: &Options,
+    terminal: &Terminal,
+) -> Result<Rendered, String> {
+    let img = image::open(path).map_err(|e| e.to_string())?;
+    render_image(&img, options, terminal)
+}
+
+fn render_image(
+    img: &DynamicImage,
+    options: &Options,
+    terminal: &Terminal,
+) -> Result<Rendered, String> {
+    let cell = if terminal.cell_width > 0 && terminal.cell_height > 0 {
+        (terminal.cell_width as u32, terminal.cell_height as u32)
+    } else {
+        FALLBACK_CELL
+    };
+    let (cols, rows) = match options.width {
+        Some(width) => scale(img, cell, width),
+        None => fit(img, cell, terminal),
+    };
+
+    let backend = options.backend;
+    let colors = options.colors;
+    let inner = match backend {
+        Backend::Kitty => kitty(img, cols, rows, cell)?,
+        Backend::Iterm => iterm(img, cols, rows, cell)?,
+        Backend::Sixel => sixel(img, cols, rows, cell),
+        Backend::HalfBlock => half_block(img, cols, rows, colors),
+        Backend::Braille => braille(img, cols, rows, colors),
+        Backend::Ascii => ascii(img, cols, rows, colors),
+    };
+
+    // The graphics backends all leave the cursor where the image started,
+    // so pad with newlines to make the art behave like `rows` lines of text.
+    let inner = if backend.is_graphics() {
+        format!("{}{}", inner, "\n".repeat(rows as usize - 1))
+    } else {
+        inner
+    };
+
+    Ok(Rendered {
+        inner,
+        width: cols as i32,
+        height: rows as i32,
+    })
+}
+
+/// Works out how many cells the image should cover. Like neofetch, the
+/// image takes up to half the terminal's height, and never more than half
+/// its width. Without a terminal, it sizes the image for an 80x24 one.
+fn fit(img: &DynamicImage, cell: (u32, u32), terminal: &Terminal) -> (u32, u32) {
+    let (w, h) = (img.width().max(1) as f64, img.height().max(1) as f64);
+    let (cell_w, cell_h) = (cell.0 as f64, cell.1 as f64);
+    let (width, height) = match (terminal.width, terminal.height) {
+        (width, height) if width > 0 && height > 0 => (width, height),
+        _ => (80, 24),
+    };
+    let max_rows = (height.max(2) / 2) as f64;
+    let max_cols = (width.max(2) / 2) as f64;
+
+    let mut rows = max_rows;
+    let mut cols = rows * cell_h * w / (h * cell_w);
+    if cols > max_cols {
+        cols = max_cols;
+        rows = cols * cell_w * h / (w * cell_h);
+    }
+    ((cols.round() as u32).max(1), (rows.round() as u32).max(1))
+}
+
+/// Works out how many rows an image `width` columns wide needs to keep its
+/// aspect ratio, given that cells are taller than they are wide.
+fn scale(img: &DynamicImage, cell: (u32, u32), width: u32) -> (u32, u32) {
+    let (w, h) = (img.width().max(1) as f64, img.height().max(1) as f64);
+    let cols = width.max(1);
+    let rows = cols as f64 * cell.0 as f64 * h / (w * cell.1 as f64);
+    (cols, (rows.round() as u32).max(1))
+}
+
+fn png(img: &DynamicImage, width: u32, height: u32) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    img.resize_exact(width, height, FilterType::Triangle)
+        .write_to(&mut Cursor::new(&mut bytes), ImageOutputFormat::Png)
+        .map_err(|e| e.to_string())?;
+    Ok(bytes)
+}
+
+/// Kitty graphics protocol, see
+/// https://sw.kovidgoyal.net/kitty/graphics-protocol/.
+fn kitty(img: &DynamicImage, cols: u32, rows: u32, cell: (u32, u32)) -> Result<String, String> {
+    let data =
+        base64::engine::general_purpose::STANDARD.encode(png(img, cols * cell.0, rows * cell.1)?);
+    let chunks = data.as_bytes().chunks(KITTY_CHUNK).collect::<Vec<&[u8]>>();
+
+    let mut to_return = String::new();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = if i + 1 < chunks.len() { 1 } else { 0 };
+        // `C=1` keeps the cursor in place, `q=2` silences responses.
+        if i == 0 {
+            to_return += &format!(
+                "\u{001b}_Ga=T,f=100,c={},r={},C=1,q=2,m={};",
+                cols, rows, more
+            );
+        } else {
+            to_return += &format!("\u{001b}_Gm={};", more);
+        }
+        to_return += std::str::from_utf8(chunk).unwrap_or_default();
+        to_return += "\u{001b}\\";
+    }
+    Ok(to_return)
+}
+
+/// iTerm2 inline images, also understood by WezTerm and mintty.
+fn iterm(img: &DynamicImage, cols: u32, rows: u32, cell: (u32, u32)) -> Result<String, String> {
+    let bytes = png(img, cols * cell.0, rows * cell.1)?;
+    Ok(format!(
+        "\u{001b}]1337;File=inline=1;size={};width={};height={};preserveAspectRatio=0;doNotMoveCursor=1:{}\u{0007}",
+        bytes.len(),
+        cols,
+        rows,
+        base64::engine::general_purpose::STANDARD.encode(&bytes),
+    ))
+}
+
+/// Sixel, quantized to the 6x6x6 color cube. Transparent pixels are left
+/// unpainted. Terminals don't agree on where the cursor goes after a sixel
+/// image, so it's saved before and put back after, like kitty's `C=1`.
+fn sixel(img: &DynamicImage, cols: u32, rows: u32, cell: (u32, u32)) -> String {
+    let (width, height) = (cols * cell.0, rows * cell.1);
+    let pixels = img
+        .resize_exact(width, height, FilterType::Triangle)
+        .to_rgba8();
+
+    let level = |v: u8| (v as u32 * 5 + 127) / 255;
+    let index = |p: [u8; 4]| -> Option<u32> {
+        if p[3] < 128 {
+            None
+        } else {
+            Some(level(p[0]) * 36 + level(p[1]) * 6 + level(p[2]))
+        }
+    };
+
+    // Scroll the image's rows into view first with index (`ESC D`), which
+    // keeps the column, so the saved cursor is still right afterwards.
+    let mut to_return = "\u{001b}D".repeat(rows as usize - 1);
+    if rows > 1 {
+        to_return += &format!("\u{001b}[{}A", rows - 1);
+    }
+    // `P2=1` makes unpainted pixels transparent.
+    to_return += &format!("\u{001b}7\u{001b}P0;1;0q\"1;1;{};{}", width, height);
+    for i in 0..216u32 {
+        let percent = |v: u32| v * 100 / 5;
+        to_return += &format!(
+            "#{};2;{};{};{}",
+            i,
+            percent(i / 36),
+            percent(i / 6 % 6),
+            percent(i % 6)
+        );
+    }
+
+    let bands = height.div_ceil(6);
+    for band in 0..bands {
+        // Map each color used in this band to its sixel column bits.
+        let mut colors: BTreeMap<u32, Vec<u8>> = BTreeMap::new();
+        for dy in 0..6 {
+            let y = band * 6 + dy;
+            if y >= height {
+                break;
+            }
+            for x in 0..width {
+                if let Some(color) = index(pixels.get_pixel(x, y).0) {
+                    colors
+                        .entry(color)
+                        .or_insert_with(|| vec![0; width as usize])[x as usize] |= 1 << dy;
+                }
+            }
+        }
+        for (color, bits) in colors.iter() {
+            to_return += &format!("#{}", color);
+            let mut x = 0;
+            while x < bits.len() {
+                let mut run = 1;
+                while x + run < bits.len() && bits[x + run] == bits[x] {
+                    run += 1;
+                }
+                let c = (63 + bits[x]) as char;
+                if run > 3 {
+                    to_return += &format!("!{}{}", run, c);
+                } else {
+                    to_return.extend(std::iter::repeat_n(c, run));
+                }
+                x += run;
+            }
+            to_return.push('$');
+        }
+        // No new line after the last band, so nothing can scroll.
+        if band + 1 < bands {
+            to_return.push('-');
+        }
+    }
+    to_return += "\u{001b}\\\u{001b}8";
+    to_return
+}
+
+fn pixel(img: &RgbaImage, x: u32, y: u32) -> Option<[u8; 3]> {
+    if x >= img.width() || y >= img.height() {
+        return None;
+    }
+    let p = img.get_pixel(x, y).0;
+    if p[3] < 128 {
+        None
+    } else {
+        Some([p[0], p[1], p[2]])
+    }
+}
+
+/// Two pixels per cell using `▀`, foreground on top and background below.
+fn half_block(img: &DynamicImage, cols: u32, rows: u32, colors: ColorSupport) -> String {
+    let pixels = img
+        .resize_exact(cols, rows * 2, FilterType::Triangle)
+        .to_rgba8();
+    let mut to_return = String::new();
+    for row in 0..rows {
+        for x in 0..cols {
+            let top = pixel(&pixels, x, row * 2);
+            let bottom = pixel(&pixels, x, row * 2 + 1);
+            to_return += &match (top, bottom) {
+                (Some(t), Some(b)) => format!("{}{}▀", fg(colors, t), bg(colors, b)),
+                (Some(t), None) => format!("\u{001b}[49m{}▀", fg(colors, t)),
+                (None, Some(b)) => format!("\u{001b}[49m{}▄", fg(colors, b)),
+                (None, None) => String::from("\u{001b}[0m "),
+            };
+        }
+        to_return += "\u{001b}[0m";
+        if row + 1 < rows {
+            to_return.push('\n');
+        }
+    }
+    to_return
+}
+
+/// Eight dots per cell using braille patterns, colored with the average of
+/// the lit dots.
+fn braille(img: &DynamicImage, cols: u32, rows: u32, colors: ColorSupport) -> String {
+    const DOTS: [[u32; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+
+    let pixels = img
+        .resize_exact(cols * 2, rows * 4, FilterType::Triangle)
+        .to_rgba8();
+    let luma = |p: [u8; 3]| 0.2126 * p[0] as f64 + 0.7152 * p[1] as f64 + 0.0722 * p[2] as f64;
+
+    // Light up everything brighter than the average opaque pixel.
+    let opaque = pixels
+        .pixels()
+        .filter(|p| p.0[3] >= 128)
+        .map(|p| luma([p.0[0], p.0[1], p.0[2]]))
+        .collect::<Vec<f64>>();
+    let threshold = opaque.iter().sum::<f64>() / opaque.len().max(1) as f64;
+
+    let mut to_return = String::new();
+    for row in 0..rows {
+        for col in 0..cols {
+            let mut bits = 0;
+            let mut sum = [0u32; 3];
+            let mut lit = 0;
+            for (dx, column) in DOTS.iter().enumerate() {
+                for (dy, bit) in column.iter().enumerate() {
+                    if let Some(p) = pixel(&pixels, col * 2 + dx as u32, row * 4 + dy as u32) {
+                        if luma(p) >= threshold {
+                            bits |= bit;
+                            for c in 0..3 {
+                                sum[c] += p[c] as u32;
+                            }
+                            lit += 1;
+                        }
+                    }
+                }
+            }
+            match sum.map(|c| c.checked_div(lit)) {
+                [Some(r), Some(g), Some(b)] => {
+                    to_return += &fg(colors, [r as u8, g as u8, b as u8]);
+                    to_return.push(char::from_u32(0x2800 + bits).unwrap_or(' '));
+                }
+                _ => to_return.push(' '),
+            }
+        }
+        to_return += "\u{001b}[0m";
+        if row + 1 < rows {
+            to_return.push('\n');
+        }
+    }
+    to_return
+}
+
+/// One character per cell, picked from a brightness ramp and colored with
+/// the pixel's color.
+fn ascii(img: &DynamicImage, cols: u32, rows: u32, colors: ColorSupport) -> String {
+    let pixels = img
+        .resize_exact(cols, rows, FilterType::Triangle)
+        .to_rgba8();
+
+    let mut to_return = String::new();
+    for row in 0..rows {
+        for col in 0..cols {
+            match pixel(&pixels, col, row) {
+                Some(p) => {
+                    let luma = (0.2126 * p[0] as f64 + 0.7152 * p[1] as f64 + 0.0722 * p[2] as f64)
+                        / 255.0;
+                    let i = (luma * (RAMP.len() - 1) as f64).round() as usize;
+                    to_return += &fg(colors, p);
+                    to_return.push(RAMP[i.min(RAMP.len() - 1)] as char);
+                }
+                None => to_return.push(' '),
+            }
+        }
+        to_return += "\u{001b}[0m";
+        if row + 1 < rows {
+            to_return.push('\n');
+        }
+    }
+    to_return
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use image::Rgba;
+
+    #[test]
+    fn every_backend_takes_up_its_rows() {
+        let img = DynamicImage::ImageRgba8(RgbaImage::from_pixel(32, 32, Rgba([200, 40, 90, 255])));
+        let terminal = Terminal {
+            width: 80,
+            height: 24,
+            cell_width: 8,
+            cell_height: 16,
+        };
+        for backend in [
+            Backend::Kitty,
+            Backend::Sixel,
+            Backend::Iterm,
+            Backend::HalfBlock,
+            Backend::Braille,
+            Backend::Ascii,
+        ] {
+            let options = Options {
+                backend,
+                colors: ColorSupport::TrueColor,
+                width: Some(10),
+            };
+            let rendered = render_image(&img, &options, &terminal).unwrap();
+            assert_eq!(rendered.height, 5, "{:?}", backend);
+            assert_eq!(
+                rendered.inner.split('\n').count(),
+                rendered.height as usize,
+                "{:?}",
+                backend
+            );
+        }
+    }
+
+    #[test]
+    fn fits_without_a_terminal() {
+        let img = DynamicImage::ImageRgba8(RgbaImage::new(100, 100));
+        let terminal = |width, height| Terminal {
+            width,
+            height,
+            cell_width: 0,
+            cell_height: 0,
+        };
+        assert_eq!(
+            fit(&img, (10, 20), &terminal(0, 0)),
+            fit(&img, (10, 20), &terminal(80, 24))
+        );
+        assert_eq!(fit(&img, (10, 20), &terminal(80, 24)), (24, 12));
+    }
+
+    #[test]
+    fn sixel_puts_the_cursor_back() {
+        let img = DynamicImage::ImageRgba8(RgbaImage::from_pixel(8, 32, Rgba([0, 0, 0, 255])));
+        let sixel = sixel(&img, 1, 2, (8, 16));
+        assert!(sixel.starts_with("\u{001b}D\u{001b}[1A\u{001b}7\u{001b}P"));
+        assert!(sixel.ends_with("\u{001b}\\\u{001b}8"));
+    }
+}
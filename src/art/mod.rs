@@ -0,0 +1,292 @@
+pub(crate) mod graphics;
+pub(crate) mod preview;
+
+use crate::{assets, clml_rs, color, errors, info, misc, mlua, utils, Arguments, Inject};
+use ascii_art::{ArtEntry, Size};
+use assets::{ascii_art, ANSI, PRINT};
+use color::ColorSupport;
+use graphics::{Backend, Options};
+use info::distro::DistroColors;
+use info::Info;
+use misc::Terminal;
+use utils::text;
+
+use std::path::Path;
+use std::{env, fs};
+
+use clml_rs::clml;
+use mlua::prelude::*;
+
+pub(crate) struct Art {
+    inner: String,
+    width: i32,
+    height: i32,
+    /// Smaller art to switch to if this one turns out to be too wide, when
+    /// `--ascii-size` is `auto`.
+    fallback: Option<&'static str>,
+}
+
+impl Art {
+    pub fn new(info: &mut Info, arguments: &Arguments, terminal: &Terminal) -> Self {
+        let mut art = Art {
+            inner: String::new(),
+            width: 0,
+            height: 0,
+            fallback: None,
+        };
+
+        if let Some(image) = &arguments.image {
+            // Images don't carry a palette, so keep the distro's colors for
+            // the info.
+            info.distro.colors = Self::colors(info, arguments, Self::distro_art(info, arguments));
+
+            let options = Options {
+                backend: arguments
+                    .backend
+                    .as_deref()
+                    .and_then(Backend::from_name)
+                    .unwrap_or_else(|| {
+                        // Graphics protocols are nothing but escapes.
+                        if color::enabled() {
+                            Backend::detect()
+                        } else {
+                            Backend::Ascii
+                        }
+                    }),
+                colors: arguments
+                    .image_colors
+                    .as_deref()
+                    .and_then(ColorSupport::from_name)
+                    .unwrap_or_else(ColorSupport::current),
+                width: arguments.image_width,
+            };
+            match graphics::render(Path::new(image), &options, terminal) {
+                Ok(rendered) => {
+                    // Escape sequences for images don't measure well, so
+                    // take the size in cells straight from the renderer.
+                    art.inner = rendered.inner;
+                    art.width = rendered.width;
+                    art.height = rendered.height;
+                    return art;
+                }
+                Err(e) => {
+                    errors::handle(&format!(
+                        "{}{}{}{}",
+                        errors::io::READ.0,
+                        image,
+                        errors::io::READ.1,
+                        e
+                    ));
+                    panic!()
+                }
+            }
+        }
+
+        let config = Path::new("/home/")
+            .join(env::var("USER").unwrap_or_default())
+            .join(".config/freshfetch");
+
+        art.inner = if let Some(path) = &arguments.ascii {
+            // The distro still decides the colors of custom art.
+            info.distro.colors = Self::colors(info, arguments, Self::distro_art(info, arguments));
+            Self::read_file(Path::new(path), &info.distro.colors)
+        } else if arguments.ascii_distro.is_some() {
+            let entry = Self::distro_art(info, arguments);
+            info.distro.colors = Self::colors(info, arguments, entry);
+            art.sized(entry, arguments)
+        } else if let Some(script) = arguments.preset.as_ref().and_then(|p| p.art.as_deref()) {
+            Self::exec_lua(script).unwrap_or_else(|e| {
+                errors::handle(&format!("{}{}", errors::LUA, e));
+                panic!()
+            })
+        } else {
+            let path = config.join("art.lua");
+
+            if path.exists() {
+                match fs::read_to_string(&path) {
+                    Ok(file) => Self::exec_lua(&file).unwrap_or_else(|e| {
+                        errors::handle(&format!("{}{}", errors::LUA, e));
+                        panic!()
+                    }),
+                    Err(e) => {
+                        errors::handle(&format!(
+                            "{}~/.config/freshfetch/art.lua{}{}",
+                            errors::io::READ.0,
+                            errors::io::READ.1,
+                            e
+                        ));
+                        panic!()
+                    }
+                }
+            } else {
+                let entry = Self::distro_art(info, arguments);
+                info.distro.colors = Self::colors(info, arguments, entry);
+                match ["art.clml", "art.txt"]
+                    .iter()
+                    .map(|name| config.join(name))
+                    .find(|path| path.exists())
+                {
+                    Some(path) => Self::read_file(&path, &info.distro.colors),
+                    None => art.sized(entry, arguments),
+                }
+            }
+        };
+
+        art.measure();
+        art
+    }
+
+    /// The built-in art to use: `--ascii_distro`'s if given, otherwise the
+    /// running distro's.
+    fn distro_art(info: &Info, arguments: &Arguments) -> &'static ArtEntry {
+        match &arguments.ascii_distro {
+            Some(name) => ArtEntry::find(name),
+            None => {
+                let id_like = info
+                    .distro
+                    .id_like
+                    .iter()
+                    .map(String::as_str)
+                    .collect::<Vec<&str>>();
+                ArtEntry::by_distro(
+                    info.distro.os_name.as_deref(),
+                    info.distro.id.as_deref(),
+                    &id_like,
+                    &info.distro.short_name,
+                )
+            }
+        }
+    }
+
+    /// The colors to use with `entry`. The distro's os-release `ANSI_COLOR`
+    /// fills in any the art doesn't set, or all of them with `--os-colors`,
+    /// unless the art is another distro's from `--ascii_distro`.
+    fn colors(info: &Info, arguments: &Arguments, entry: &ArtEntry) -> DistroColors {
+        let accent = match arguments.ascii_distro {
+            Some(_) => None,
+            None => info.distro.ansi_color,
+        };
+        DistroColors::with_accent(entry.colors, accent, arguments.os_colors)
+    }
+
+    /// Picks the variant of `entry` asked for with `--ascii-size`. Art
+    /// without that variant is used as is. In `auto` mode, large art keeps
+    /// its small variant around for `fit`.
+    fn sized(&mut self, entry: &'static ArtEntry, arguments: &Arguments) -> String {
+        match arguments.ascii_size.as_deref().and_then(Size::from_name) {
+            Some(size) => entry.variant(size).unwrap_or(entry).art.into(),
+            None => {
+                if entry.size == Size::Large {
+                    self.fallback = entry.variant(Size::Small).map(|v| v.art);
+                }
+                entry.art.into()
+            }
+        }
+    }
+
+    /// Switches to the small art if the current one is wider than
+    /// `available` columns.
+    pub fn fit(&mut self, available: i32) {
+        if self.width > available {
+            if let Some(fallback) = self.fallback.take() {
+                self.inner = fallback.into();
+                self.measure();
+            }
+        }
+    }
+
+    /// Reads art from a `.clml` or plain text file. Neofetch's `${c1}` to
+    /// `${c6}` placeholders are swapped for the distro's colors, with `c5` and
+    /// `c6` reusing the last one, so neofetch art can be used as is.
+    fn read_file(path: &Path, colors: &DistroColors) -> String {
+        let contents = fs::read_to_string(path).unwrap_or_else(|e| {
+            errors::handle(&format!(
+                "{}{}{}{}",
+                errors::io::READ.0,
+                path.to_string_lossy(),
+                errors::io::READ.1,
+                e
+            ));
+            panic!()
+        });
+        let contents = if path.extension().map(|e| e == "clml").unwrap_or(false) {
+            clml(&contents)
+        } else {
+            contents
+        };
+
+        let mut to_return = contents.trim_end_matches('\n').to_string();
+        for (i, color) in [
+            &colors.0, &colors.1, &colors.2, &colors.3, &colors.3, &colors.3,
+        ]
+        .iter()
+        .enumerate()
+        {
+            to_return = to_return.replace(&format!("${{c{}}}", i + 1), &color.to_string());
+        }
+        to_return
+    }
+
+    #[inline(always)]
+    fn exec_lua(script: &str) -> Result<String, String> {
+        let mut lua = Lua::new();
+
+        lua.load(PRINT)
+            .exec()
+            .map_err(|e| format!("{}{}", errors::LUA, e))?;
+        lua.load(ANSI)
+            .exec()
+            .map_err(|e| format!("{}{}", errors::LUA, e))?;
+        ColorSupport::current().inject(&mut lua);
+        lua.load(script)
+            .exec()
+            .map_err(|e| format!("{}{}", errors::LUA, e))?;
+
+        let bind = lua
+            .globals()
+            .get::<_, String>("__freshfetch__")
+            .map_err(|e| format!("{}{}", errors::LUA, e));
+
+        bind
+    }
+
+    #[inline(always)]
+    fn measure(&mut self) {
+        let (w, h) = text::measure(&self.inner);
+        self.width = w as i32;
+        self.height = h as i32;
+    }
+}
+
+/// Prints every built-in art name and its aliases, and with `preview`, the
+/// art itself.
+pub(crate) fn list(preview: bool) {
+    let mut entries = ascii_art::ASCII_ART.iter().collect::<Vec<&ArtEntry>>();
+    entries.sort_by_key(|entry| entry.name);
+
+    for entry in entries {
+        let name = format!("\u{001b}[1m{}\u{001b}[0m", entry.name);
+        if entry.aliases.is_empty() {
+            println!("{}", color::filter(&name));
+        } else {
+            println!("{} ({})", color::filter(&name), entry.aliases.join(", "));
+        }
+        if preview {
+            let art = format!("{}\u{001b}[0m", entry.art.trim_end_matches('\n'));
+            println!("{}\n", color::filter(&art));
+        }
+    }
+}
+
+impl Inject for Art {
+    #[inline(always)]
+    fn inject(&self, lua: &mut Lua) {
+        let globals = lua.globals();
+        if globals.set("art", self.inner.as_str()).is_err()
+            || globals.set("artWidth", self.width).is_err()
+            || globals.set("artHeight", self.height).is_err()
+        {
+            errors::handle("Failed to inject art into Lua globals.");
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use crate::{assets, color, info, misc, utils};
+use assets::ascii_art::{ArtEntry, ASCII_ART};
+use info::distro::DistroColors;
+use misc::Terminal;
+use utils::text;
+
+/// Columns between arts in the grid.
+const GAP: usize = 4;
+
+/// A single art, split into lines that can be printed on their own.
+struct Cell {
+    lines: Vec<String>,
+    width: usize,
+}
+
+impl Cell {
+    fn new(entry: &ArtEntry) -> Self {
+        let colors = DistroColors::from(entry.colors);
+        let swatches = [&colors.0, &colors.1, &colors.2, &colors.3]
+            .iter()
+            .map(|c| format!("{}███\u{001b}[0m", c))
+            .collect::<Vec<String>>()
+            .join(" ");
+
+        let mut lines = vec![
+            format!("\u{001b}[1m{}\u{001b}[0m", entry.name),
+            swatches,
+            String::new(),
+        ];
+        lines.extend(text::carry_sgr(entry.art.trim_end_matches('\n')));
+        let width = lines
+            .iter()
+            .map(|l| text::display_width(l))
+            .max()
+            .unwrap_or(0);
+        Cell { lines, width }
+    }
+}
+
+/// Matches `text` against a glob where `*` is any run of characters and
+/// `?` is any one character. Case doesn't matter.
+fn glob(pattern: &str, text: &str) -> bool {
+    let pattern = pattern.to_lowercase().chars().collect::<Vec<char>>();
+    let text = text.to_lowercase().chars().collect::<Vec<char>>();
+
+    let (mut p, mut t) = (0, 0);
+    // Where to resume from after the last `*`.
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = star {
+            p = star_p + 1;
+            t = star_t + 1;
+            star = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Prints every art matching `filter` (by name or alias) in a grid as wide
+/// as the terminal, followed by how many of them have been colorized.
+pub(crate) fn preview(filter: Option<&str>, terminal: &Terminal) {
+    let mut entries = ASCII_ART
+        .iter()
+        .filter(|entry| match filter {
+            Some(filter) => {
+                glob(filter, entry.name) || entry.aliases.iter().any(|a| glob(filter, a))
+            }
+            None => true,
+        })
+        .collect::<Vec<&ArtEntry>>();
+    entries.sort_by_key(|entry| entry.name);
+
+    let cells = entries.iter().map(|e| Cell::new(e)).collect::<Vec<Cell>>();
+    let max_width = if terminal.width > 0 {
+        terminal.width as usize
+    } else {
+        80
+    };
+
+    let mut row: Vec<&Cell> = Vec::new();
+    let mut row_width = 0;
+    for cell in cells.iter() {
+        if !row.is_empty() && row_width + GAP + cell.width > max_width {
+            print_row(&row);
+            row.clear();
+            row_width = 0;
+        }
+        row_width += if row.is_empty() { 0 } else { GAP } + cell.width;
+        row.push(cell);
+    }
+    if !row.is_empty() {
+        print_row(&row);
+    }
+
+    // Art that's never been given colors is still on the default white.
+    let colorized = entries
+        .iter()
+        .filter(|entry| entry.colors != ArtEntry::DEFAULT_COLORS)
+        .count();
+    println!("{}/{} colorized", colorized, entries.len());
+}
+
+fn print_row(row: &[&Cell]) {
+    let height = row.iter().map(|c| c.lines.len()).max().unwrap_or(0);
+    for i in 0..height {
+        let mut line = String::new();
+        for (j, cell) in row.iter().enumerate() {
+            let content = cell.lines.get(i).map(String::as_str).unwrap_or("");
+            line += content;
+            if j + 1 < row.len() {
+                line += &" ".repeat(cell.width - text::display_width(content) + GAP);
+            }
+        }
+        println!("{}", color::filter(&line));
+    }
+    println!();
+}
@@ -1,81 +1,95 @@
-function reset() return "\x1b[0m" end
+-- Every escape goes through here, so none are output when colorEnabled is
+-- false: with --color=never, NO_COLOR, or when stdout isn't a terminal.
+local function esc(s)
+	if colorEnabled == false then return "" end
+	return "\x1b"..s
+end
 
-function bold() return "\x1b[1m" end
+function reset() return esc("[0m") end
 
-function dim() return "\x1b[2m" end
+function bold() return esc("[1m") end
 
-function red() return "\x1b[38;5;1m" end
+function dim() return esc("[2m") end
 
-function redBright() return "\x1b[38;5;9m" end
+function red() return esc("[31m") end
 
-function yellow() return "\x1b[38;5;3m" end
+function redBright() return esc("[91m") end
 
-function yellowBright() return "\x1b[38;5;11m" end
+function yellow() return esc("[33m") end
 
-function green() return "\x1b[38;5;2m" end
+function yellowBright() return esc("[93m") end
 
-function greenBright() return "\x1b[38;5;10m" end
+function green() return esc("[32m") end
 
-function cyan() return "\x1b[38;5;6m" end
+function greenBright() return esc("[92m") end
 
-function cyanBright() return "\x1b[38;5;14m" end
+function cyan() return esc("[36m") end
 
-function blue() return "\x1b[38;5;4m" end
+function cyanBright() return esc("[96m") end
 
-function blueBright() return "\x1b[38;5;12m" end
+function blue() return esc("[34m") end
 
-function magenta() return "\x1b[38;5;5m" end
+function blueBright() return esc("[94m") end
 
-function magentaBright() return "\x1b[38;5;13m" end
+function magenta() return esc("[35m") end
 
-function black() return "\x1b[38;5;0m" end
+function magentaBright() return esc("[95m") end
 
-function blackBright() return "\x1b[38;5;8m" end
+function black() return esc("[30m") end
 
-function white() return "\x1b[38;5;7m" end
+function blackBright() return esc("[90m") end
 
-function whiteBright() return "\x1b[38;5;15m" end
+function white() return esc("[37m") end
 
-function redBg() return "\x1b[48;5;1m" end
+function whiteBright() return esc("[97m") end
 
-function redBrightBg() return "\x1b[48;5;9m" end
+function redBg() return esc("[41m") end
 
-function yellowBg() return "\x1b[48;5;3m" end
+function redBrightBg() return esc("[101m") end
 
-function yellowBrightBg() return "\x1b[48;5;11m" end
+function yellowBg() return esc("[43m") end
 
-function greenBg() return "\x1b[48;5;2m" end
+function yellowBrightBg() return esc("[103m") end
 
-function greenBrightBg() return "\x1b[48;5;10m" end
+function greenBg() return esc("[42m") end
 
-function cyanBg() return "\x1b[48;5;6m" end
+function greenBrightBg() return esc("[102m") end
 
-function cyanBrightBg() return "\x1b[48;5;14m" end
+function cyanBg() return esc("[46m") end
 
-function blueBg() return "\x1b[48;5;4m" end
+function cyanBrightBg() return esc("[106m") end
 
-function blueBrightBg() return "\x1b[48;5;12m" end
+function blueBg() return esc("[44m") end
 
-function magentaBg() return "\x1b[48;5;5m" end
+function blueBrightBg() return esc("[104m") end
 
-function magentaBrightBg() return "\x1b[48;5;13m" end
+function magentaBg() return esc("[45m") end
 
-function blackBg() return "\x1b[48;5;0m" end
+function magentaBrightBg() return esc("[105m") end
 
-function blackBrightBg() return "\x1b[48;5;8m" end
+function blackBg() return esc("[40m") end
 
-function whiteBg() return "\x1b[48;5;7m" end
+function blackBrightBg() return esc("[100m") end
 
-function whiteBrightBg() return "\x1b[48;5;15m" end
+function whiteBg() return esc("[47m") end
 
-function up(a) return "\x1b["..a.."A" end
+function whiteBrightBg() return esc("[107m") end
 
-function down(a) return "\x1b["..a.."B" end
+function up(a) return esc("["..a.."A") end
 
-function left(a) return "\x1b["..a.."D" end
+function down(a) return esc("["..a.."B") end
 
-function right(a) return "\x1b["..a.."C" end
+function left(a) return esc("["..a.."D") end
 
-function save() return "\x1b[s" end
+function right(a) return esc("["..a.."C") end
 
-function restore() return "\x1b[u" end
+function save() return esc("[s") end
+
+function restore() return esc("[u") end
+
+-- Markers compose{} looks for to decide whether an info line that's too wide
+-- gets wrapped or cut off. See `Overflow` in src/layout/compose.rs.
+
+function wrap() return "\x1b_freshfetch:wrap\x1b\\" end
+
+function truncate() return "\x1b_freshfetch:truncate\x1b\\" end
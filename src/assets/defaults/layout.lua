@@ -1,20 +1,8 @@
-print(""..save())
-
-print("  "..string.gsub(art, "\n", "\n  "))
-
-print(""..up(artHeight))
-
-print(""
-	..right(artWidth + 4)
-	..string.gsub(info,
-		"\n",
-		"\n"..right(artWidth + 4)))
-
-print(""..restore())
-
-if infoHeight > artHeight then
-	print(""..down(infoHeight))
-else
-	print(""..down(artHeight))
-end
-
+print(compose{
+	art = art,
+	artWidth = artWidth,
+	artHeight = artHeight,
+	info = info,
+	margin = 2,
+	gap = 2,
+})
@@ -1,5 +1,6 @@
 pub(crate) mod ascii_art;
 pub(crate) mod defaults;
+pub(crate) mod presets;
 
 pub(crate) static ANSI: &str = include_str!("./ansi.lua");
 pub(crate) static PRINT: &str = include_str!("./print.lua");
@@ -0,0 +1,34 @@
+-- Draws a box around the info, using whatever info.lua is in use.
+local lines = {}
+for line in (info:gsub("\n+$", "").."\n"):gmatch("(.-)\n") do
+	table.insert(lines, line)
+end
+local width = 0
+for _, line in ipairs(lines) do
+	width = math.max(width, displayWidth(line))
+end
+
+local box = { "╭"..string.rep("─", width + 2).."╮" }
+for _, line in ipairs(lines) do
+	table.insert(box, ""
+		.."│ "
+		..line
+		..reset()
+		..string.rep(" ", width - displayWidth(line))
+		.." │")
+end
+table.insert(box, "╰"..string.rep("─", width + 2).."╯")
+
+-- Put the box under the art when they don't fit side by side, rather than
+-- cutting off its right edge.
+local fits = terminal.width <= 0 or 2 + artWidth + 2 + width + 4 <= terminal.width
+print(compose{
+	art = art,
+	artWidth = artWidth,
+	artHeight = artHeight,
+	info = table.concat(box, "\n"),
+	margin = 2,
+	gap = fits and 2 or 1,
+	align = fits and "left" or "top",
+	valign = "center",
+})
@@ -0,0 +1,118 @@
+-- user@host, with a line as long as it underneath.
+if context ~= nil then
+	print(""
+		..bold()
+		..distroColors[1]
+		..context.user
+		..reset()
+		.."@"
+		..bold()
+		..distroColors[1]
+		..context.host
+		..reset())
+	print(string.rep("-", displayWidth(context.user.."@"..context.host)))
+end
+
+-- Joins the parts that aren't empty with spaces.
+local function words(...)
+	local parts = {}
+	for _, part in pairs({...}) do
+		if part ~= "" then
+			table.insert(parts, tostring(part))
+		end
+	end
+	return table.concat(parts, " ")
+end
+
+local function plural(n, unit)
+	if n ~= 1 then
+		return n.." "..unit.."s"
+	end
+	return n.." "..unit
+end
+
+local function uptimeText()
+	if uptime == nil then return nil end
+	local parts = {}
+	if uptime.days >= 1 then
+		table.insert(parts, plural(uptime.days, "day"))
+	end
+	if uptime.hours >= 1 then
+		table.insert(parts, plural(uptime.hours, "hour"))
+	end
+	if uptime.minutes >= 1 then
+		table.insert(parts, plural(uptime.minutes, "min"))
+	elseif #parts == 0 then
+		table.insert(parts, plural(uptime.seconds, "sec"))
+	end
+	return table.concat(parts, ", ")
+end
+
+local function packages()
+	if packageManagers == nil then return nil end
+	local parts = {}
+	for _, packageManager in ipairs(packageManagers) do
+		if packageManager.packages ~= 0 then
+			table.insert(parts, packageManager.packages.." ("..packageManager.name..")")
+		end
+	end
+	return table.concat(parts, ", ")
+end
+
+local function resolutionText()
+	if resolution == nil then return nil end
+	local size = resolution.width.."x"..resolution.height
+	if resolution.refresh ~= nil then
+		return size.." @ "..math.floor(resolution.refresh + 0.5).."Hz"
+	end
+	return size
+end
+
+local function cpuText()
+	if cpu == nil then return nil end
+	local freq = (cpu.freq >= 1000)
+		and ""..(cpu.freq / 1000).."GHz"
+		or  ""..cpu.freq.."MHz"
+	return cpu.name.." ("..cpu.cores..") @ "..freq
+end
+
+line("OS", distro and words(distro.fullname, distro.architecture))
+line("Host", host and words(host.model, host.version))
+line("Kernel", kernel and kernel.version)
+line("Uptime", uptimeText())
+line("Packages", packages())
+line("Shell", shell and words(shell.name, shell.version))
+line("Resolution", resolutionText())
+line("DE", de and words(de.name, de.version))
+line("WM", wm)
+line("CPU", cpuText())
+-- One line per GPU.
+if gpus ~= nil then
+	for _, gpu in ipairs(gpus) do
+		line("GPU", words(gpu.brand, gpu.name))
+	end
+end
+line("Memory", memory and math.floor(memory.used / 1024).."MiB / "..math.floor(memory.max / 1024).."MiB")
+
+-- Palette
+print("")
+print(""
+	..blackBg()  .."   "
+	..redBg()    .."   "
+	..greenBg()  .."   "
+	..yellowBg() .."   "
+	..blueBg()   .."   "
+	..magentaBg().."   "
+	..cyanBg()   .."   "
+	..whiteBg()  .."   "
+	..reset())
+print(""
+	..blackBrightBg()  .."   "
+	..redBrightBg()    .."   "
+	..greenBrightBg()  .."   "
+	..yellowBrightBg() .."   "
+	..blueBrightBg()   .."   "
+	..magentaBrightBg().."   "
+	..cyanBrightBg()   .."   "
+	..whiteBrightBg()  .."   "
+	..reset())
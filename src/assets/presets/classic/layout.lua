@@ -0,0 +1,7 @@
+print(compose{
+	art = art,
+	artWidth = artWidth,
+	artHeight = artHeight,
+	info = info,
+	gap = 3,
+})
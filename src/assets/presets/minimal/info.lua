@@ -0,0 +1,30 @@
+local function uptimeText()
+	if uptime == nil then return nil end
+	local parts = {}
+	if uptime.days >= 1 then table.insert(parts, uptime.days.."d") end
+	if uptime.hours >= 1 then table.insert(parts, uptime.hours.."h") end
+	if uptime.minutes >= 1 or #parts == 0 then table.insert(parts, uptime.minutes.."m") end
+	return table.concat(parts, " ")
+end
+
+local function packages()
+	if packageManagers == nil then return nil end
+	local total = 0
+	for _, packageManager in ipairs(packageManagers) do
+		total = total + packageManager.packages
+	end
+	return total
+end
+
+-- Lowercase labels lined up in one column, and nothing else.
+local function item(label, value)
+	line(label, value, { separator = "  ", align = true })
+end
+
+item("os", distro and distro.shortname)
+item("kernel", kernel and kernel.version)
+item("uptime", uptimeText())
+item("pkgs", packages())
+item("shell", shell and shell.name)
+item("wm", wm)
+item("memory", memory and math.floor(memory.used / 1024).."M / "..math.floor(memory.max / 1024).."M")
@@ -0,0 +1,7 @@
+-- No art, just the info.
+print(compose{
+	art = "",
+	info = info,
+	margin = 2,
+	gap = 0,
+})
@@ -0,0 +1,49 @@
+use crate::assets::defaults;
+
+/// A built-in look for `--preset`. Whatever it leaves out is taken from the
+/// config dir or the defaults, the same as without `--preset`.
+pub(crate) struct PresetEntry {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub info: Option<&'static str>,
+    pub layout: Option<&'static str>,
+}
+
+pub(crate) static PRESETS: &[PresetEntry] = &[
+    PresetEntry {
+        name: "default",
+        description: "What freshfetch looks like without a config.",
+        info: Some(defaults::INFO),
+        layout: Some(defaults::LAYOUT),
+    },
+    PresetEntry {
+        name: "classic",
+        description: "Neofetch's look, with a line under user@host.",
+        info: Some(include_str!("./classic/info.lua")),
+        layout: Some(include_str!("./classic/layout.lua")),
+    },
+    PresetEntry {
+        name: "minimal",
+        description: "A few lowercase lines and no art.",
+        info: Some(include_str!("./minimal/info.lua")),
+        layout: Some(include_str!("./minimal/layout.lua")),
+    },
+    PresetEntry {
+        name: "boxed",
+        description: "Draws a box around the info.",
+        info: None,
+        layout: Some(include_str!("./boxed/layout.lua")),
+    },
+    PresetEntry {
+        name: "nerd",
+        description: "Nerd Font icons next to every line.",
+        info: Some(include_str!("./nerd/info.lua")),
+        layout: Some(include_str!("./nerd/layout.lua")),
+    },
+    PresetEntry {
+        name: "pfetch",
+        description: "Short labels and a row of colors, like pfetch.",
+        info: Some(include_str!("./pfetch/info.lua")),
+        layout: Some(include_str!("./pfetch/layout.lua")),
+    },
+];
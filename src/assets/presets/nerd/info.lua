@@ -0,0 +1,109 @@
+-- Needs a Nerd Font for the icons.
+
+if context ~= nil then
+	section(context.user.."@"..context.host, { icon = distroColors[1].."󰀄"..reset() })
+end
+
+-- Joins the parts that aren't empty with spaces.
+local function words(...)
+	local parts = {}
+	for _, part in pairs({...}) do
+		if part ~= "" then
+			table.insert(parts, tostring(part))
+		end
+	end
+	return table.concat(parts, " ")
+end
+
+local function plural(n, unit)
+	if n ~= 1 and n ~= 0 then
+		return n.." "..unit.."s"
+	end
+	return n.." "..unit
+end
+
+local function uptimeText()
+	if uptime == nil then return nil end
+	local parts = {}
+	if uptime.days >= 1 then
+		table.insert(parts, plural(uptime.days, "day"))
+	end
+	if uptime.hours >= 1 then
+		table.insert(parts, plural(uptime.hours, "hour"))
+	end
+	if uptime.minutes >= 1 then
+		table.insert(parts, plural(uptime.minutes, "minute"))
+	elseif uptime.hours == 0 then
+		table.insert(parts, plural(uptime.seconds, "second"))
+	end
+	return table.concat(parts, ", ")
+end
+
+local function packages()
+	if packageManagers == nil then return nil end
+	local parts = {}
+	for _, packageManager in ipairs(packageManagers) do
+		if packageManager.packages ~= 0 then
+			table.insert(parts, packageManager.packages.." ("..packageManager.name..")")
+		end
+	end
+	if #parts == 0 then return "0" end
+	return table.concat(parts, ", ")
+end
+
+local function resolutionText()
+	if resolution == nil then return nil end
+	local size = resolution.width.."x"..resolution.height
+	if resolution.refresh ~= nil then
+		return size.." @ "..math.floor(resolution.refresh + 0.5).."Hz"
+	end
+	return size
+end
+
+local function cpuText()
+	if cpu == nil then return nil end
+	local freq = (cpu.freq >= 1000)
+		and ""..(cpu.freq / 1000).."GHz"
+		or  ""..cpu.freq.."MHz"
+	return cpu.name.." ("..cpu.cores..") @ "..freq
+end
+
+local function gpuList()
+	if gpus == nil then return nil end
+	local list = {}
+	for _, gpu in ipairs(gpus) do
+		table.insert(list, words(gpu.brand, gpu.name))
+	end
+	return list
+end
+
+local function item(icon, label, value)
+	line(label, value, { icon = " "..distroColors[1]..icon..reset().." ", align = true })
+end
+
+item("󰋘", "OS", distro and words(distro.shortname, distro.architecture))
+item("󰇅", "Host", host and words(host.model, host.version))
+item("󰌽", "Kernel", kernel and words(kernel.name, kernel.version))
+item("󰖉", "Uptime", uptimeText())
+item("󰏗", "Packages", packages())
+item("󰞷", "Shell", shell and words(shell.name, shell.version))
+item("󰍹", "Resolution", resolutionText())
+item("󱕅", "DE", de and words(de.name, de.version))
+item("󱂬", "WM", wm)
+item("󰘚", "CPU", cpuText())
+item("󰢮", gpus ~= nil and #gpus > 1 and "GPUs" or "GPU", gpuList())
+item("󰐿", "Board", motherboard and words(motherboard.vendor, motherboard.name))
+item("󰍛", "Memory", memory and math.floor(memory.used / 1024).."MB / "..math.floor(memory.max / 1024).."MB")
+
+-- Palette
+print("")
+print(" "
+	..black()  .." "
+	..red()    .." "
+	..green()  .." "
+	..yellow() .." "
+	..blue()   .." "
+	..magenta().." "
+	..cyan()   .." "
+	..white()  .." "
+	..reset())
@@ -0,0 +1,8 @@
+print(compose{
+	art = art,
+	artWidth = artWidth,
+	artHeight = artHeight,
+	info = info,
+	margin = 2,
+	gap = 2,
+})
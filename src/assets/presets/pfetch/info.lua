@@ -0,0 +1,53 @@
+if context ~= nil then
+	print(""
+		..bold()
+		..distroColors[1]
+		..context.user
+		..reset()
+		..bold()
+		.."@"
+		..distroColors[1]
+		..context.host
+		..reset())
+end
+
+local function uptimeText()
+	if uptime == nil then return nil end
+	local parts = {}
+	if uptime.days >= 1 then table.insert(parts, uptime.days.."d") end
+	if uptime.hours >= 1 then table.insert(parts, uptime.hours.."h") end
+	if uptime.minutes >= 1 or #parts == 0 then table.insert(parts, uptime.minutes.."m") end
+	return table.concat(parts, " ")
+end
+
+local function packages()
+	if packageManagers == nil then return nil end
+	local total = 0
+	for _, packageManager in ipairs(packageManagers) do
+		total = total + packageManager.packages
+	end
+	return total
+end
+
+local function item(label, value)
+	line(label, value, { color = distroColors[1], separator = " ", align = true })
+end
+
+item("os", distro and distro.shortname)
+item("host", host and host.model)
+item("kernel", kernel and kernel.version)
+item("uptime", uptimeText())
+item("pkgs", packages())
+item("memory", memory and math.floor(memory.used / 1024).."M / "..math.floor(memory.max / 1024).."M")
+
+-- Palette
+print("")
+print(""
+	..red()    .."███"
+	..green()  .."███"
+	..yellow() .."███"
+	..blue()   .."███"
+	..magenta().."███"
+	..cyan()   .."███"
+	..white()  .."███"
+	..reset())
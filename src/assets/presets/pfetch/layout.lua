@@ -0,0 +1,8 @@
+print(compose{
+	art = art,
+	artWidth = artWidth,
+	artHeight = artHeight,
+	info = info,
+	margin = 1,
+	gap = 3,
+})
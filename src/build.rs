@@ -2,15 +2,13 @@
 
 extern crate clml_rs;
 
-//mod assets;
-
+use std::collections::HashSet;
+use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
 
 use clml_rs::clml;
 
-//use assets::ascii_art::{ ASCII_ART };
-
 type BuildList = Vec<(PathBuf, PathBuf)>;
 
 fn get_buildlist(base: &Path) -> BuildList {
@@ -37,6 +35,208 @@ fn get_buildlist(base: &Path) -> BuildList {
     to_return
 }
 
+/// Metadata for one piece of art, read from the `.meta` file next to its
+/// `.clml`. Each line is `key = value`, and `match` and `os_name` can be
+/// repeated:
+///
+/// ```text
+/// # Arch Linux
+/// name = arch
+/// aliases = archlinux
+/// size = large
+/// family = arch
+/// ids = arch archarm
+/// colors = 6 6 7 1
+/// match = starts_with Arch
+/// ```
+///
+/// `os_name` is an exact os-release `NAME`, for derivatives like LMDE that
+/// share their parent's `ID`.
+struct ArtMeta {
+    name: String,
+    aliases: Vec<String>,
+    size: String,
+    family: String,
+    ids: Vec<String>,
+    os_names: Vec<String>,
+    colors: Vec<String>,
+    rules: Vec<(String, String)>,
+    output: PathBuf,
+}
+
+fn get_meta(input: &Path, output: &Path) -> ArtMeta {
+    let path = input.with_extension("meta");
+    let contents = fs::read_to_string(&path)
+        .unwrap_or_else(|_| panic!("Failed to read the file \"{:?}\"!", &path));
+    let mut meta = ArtMeta {
+        name: String::new(),
+        aliases: Vec::new(),
+        size: String::from("large"),
+        family: String::new(),
+        ids: Vec::new(),
+        os_names: Vec::new(),
+        colors: Vec::new(),
+        rules: Vec::new(),
+        output: output.to_path_buf(),
+    };
+    for line in contents.lines() {
+        if line.starts_with('#') || line.trim().is_empty() {
+            continue;
+        }
+        let (key, value) = line
+            .split_once(" = ")
+            .unwrap_or_else(|| panic!("Expected `key = value` in {:?}, got \"{}\"!", &path, line));
+        let words = || value.split_whitespace().map(String::from).collect();
+        match key.trim() {
+            "name" => meta.name = value.to_string(),
+            "aliases" => meta.aliases = words(),
+            "size" => meta.size = value.to_string(),
+            "family" => meta.family = value.to_string(),
+            "ids" => meta.ids = words(),
+            "os_name" => meta.os_names.push(value.to_string()),
+            "colors" => meta.colors = words(),
+            "match" => {
+                let (check, pattern) = value.split_once(' ').unwrap_or_else(|| {
+                    panic!("Expected `match = <check> <pattern>` in {:?}!", &path)
+                });
+                meta.rules.push((check.to_string(), pattern.to_string()));
+            }
+            other => panic!("Unknown key \"{}\" in {:?}!", other, &path),
+        }
+    }
+    if meta.name.is_empty() {
+        panic!("{:?} doesn't have a name!", &path);
+    }
+    if meta.family.is_empty() {
+        meta.family = meta.name.clone();
+    }
+    meta
+}
+
+/// Generates `ASCII_ART` and `RULES` for `assets::ascii_art`.
+fn registry(metas: &[ArtMeta]) -> String {
+    // Every buildlist entry must be reachable by its name, so names and
+    // aliases have to be unique.
+    let mut seen = HashSet::new();
+    for meta in metas.iter() {
+        for name in std::iter::once(&meta.name).chain(meta.aliases.iter()) {
+            if name.to_lowercase() != *name {
+                panic!("The art name \"{}\" has to be lowercase!", name);
+            }
+            if !seen.insert(name.clone()) {
+                panic!("The art name \"{}\" is used more than once!", name);
+            }
+        }
+    }
+    for meta in metas.iter() {
+        if !seen.contains(&meta.family) {
+            panic!(
+                "\"{}\" has an unknown family \"{}\"!",
+                meta.name, meta.family
+            );
+        }
+    }
+
+    let mut to_return = String::from("pub(crate) static ASCII_ART: &[ArtEntry] = &[\n");
+    for meta in metas.iter() {
+        let list = |v: &[String]| {
+            v.iter()
+                .map(|s| format!("{:?}", s))
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+        let mut colors = meta
+            .colors
+            .iter()
+            .map(|c| match c.as_str() {
+                "reset" => String::from("Some(Color::Reset)"),
+                n => match n.parse::<u8>() {
+                    Ok(n) => format!("Some(Color::Indexed({}))", n),
+                    Err(_) => panic!("Invalid color \"{}\" for \"{}\"!", n, meta.name),
+                },
+            })
+            .collect::<Vec<String>>();
+        if colors.is_empty() {
+            colors.push(String::from("Some(Color::Indexed(7))"));
+        }
+        colors.resize(4, String::from("None"));
+        let size = match meta.size.as_str() {
+            "large" => "Size::Large",
+            "small" => "Size::Small",
+            "old" => "Size::Old",
+            other => panic!("Invalid size \"{}\" for \"{}\"!", other, meta.name),
+        };
+        let art = meta
+            .output
+            .to_string_lossy()
+            .replace("/./", "/")
+            .trim_start_matches("./")
+            .to_string();
+
+        to_return += "    ArtEntry {\n";
+        to_return += &format!("        name: {:?},\n", meta.name);
+        to_return += &format!("        aliases: &[{}],\n", list(&meta.aliases));
+        to_return += &format!("        ids: &[{}],\n", list(&meta.ids));
+        to_return += &format!("        os_names: &[{}],\n", list(&meta.os_names));
+        to_return += &format!("        colors: [{}],\n", colors.join(", "));
+        to_return += &format!("        size: {},\n", size);
+        to_return += &format!("        family: {:?},\n", meta.family);
+        to_return += &format!(
+            "        art: include_str!(concat!(env!(\"CARGO_MANIFEST_DIR\"), \"/{}\")),\n",
+            art
+        );
+        to_return += "    },\n";
+    }
+    to_return += "];\n\n";
+
+    // Exact matches win, then the longest prefix, then substrings, so a
+    // more specific rule can't be shadowed by a shorter one.
+    let mut rules = Vec::new();
+    for (i, meta) in metas.iter().enumerate() {
+        for (check, pattern) in meta.rules.iter() {
+            let (rank, check) = match check.as_str() {
+                "is" => (0, "Check::Is"),
+                "starts_with" => (1, "Check::StartsWith"),
+                "contains" => (2, "Check::Contains"),
+                other => panic!("Invalid match \"{}\" for \"{}\"!", other, meta.name),
+            };
+            rules.push((rank, check, pattern, i));
+        }
+    }
+    rules.sort_by(|a, b| a.0.cmp(&b.0).then(b.2.len().cmp(&a.2.len())));
+    to_return += "pub(crate) static RULES: &[(Check, &str, usize)] = &[\n";
+    for (_, check, pattern, i) in rules.iter() {
+        to_return += &format!("    ({}, {:?}, {}),\n", check, pattern, i);
+    }
+    to_return += "];\n";
+    to_return
+}
+
+/// Lists every art name for `--help`, wrapped and indented to match the
+/// rest of it.
+fn distro_list(metas: &[ArtMeta]) -> String {
+    let mut names = metas.iter().map(|m| m.name.as_str()).collect::<Vec<&str>>();
+    names.sort_unstable();
+
+    let mut lines = vec![String::new()];
+    for name in names.iter() {
+        let line = lines.last_mut().unwrap();
+        if !line.is_empty() && line.len() + name.len() + 2 > 72 {
+            lines.push(String::new());
+        }
+        let line = lines.last_mut().unwrap();
+        *line += name;
+        *line += ", ";
+    }
+    lines
+        .iter()
+        .map(|line| line.trim_end())
+        .collect::<Vec<&str>>()
+        .join("\n    ")
+        .trim_end_matches(',')
+        .to_string()
+}
+
 fn progress(min: usize, max: usize) -> String {
     let mut bar = String::from(" [");
     let complete = ((min as f32 / max as f32) * 57.0).floor() as usize;
@@ -69,41 +269,19 @@ fn main() {
             .unwrap_or_else(|_| panic!("Failed to write to the file \"{:?}\"!", &target.1));
     }
     println!("\u{001b}[1A\r\u{001b}[K    \u{001b}[1m\u{001b}[32mFinished\u{001b}[0m ASCII art");
+    let metas = buildlist
+        .iter()
+        .map(|target| get_meta(&target.0, &target.1))
+        .collect::<Vec<ArtMeta>>();
+    {
+        let out_dir = env::var("OUT_DIR").expect("OUT_DIR isn't set!");
+        fs::write(Path::new(&out_dir).join("ascii_art.rs"), registry(&metas))
+            .expect("Failed to write the ASCII art registry!");
+    }
     {
         let input = fs::read_to_string("./src/assets/help.clml")
             .expect("Failed to read the file \"./src/assets/help.clml\"!");
-        let /*mut*/ output = clml(&input);
-        /*// This is removed because it causes a compile issue-- Once 0.2.0 is released
-          // this will be fixed, because I'll be reworking how the ASCII art is compiled
-          // into the final build.
-         let ascii_distro_list = {
-        let mut output = clml(&input);
-        let ascii_distro_list = {
-            let mut to_use = Vec::new();
-            for art in ASCII_ART.iter() {
-                if art.0 != "" {
-                    to_use.push(String::from(art.0));
-                }
-            }
-            let mut to_return = String::new();
-            let mut this_line = String::new();
-            for (i, name) in to_use.iter().enumerate() {
-                this_line += name;
-                if i != to_use.len() {
-                    this_line += ", "
-                }
-                if this_line.len() >= 64 {
-                    to_return += &this_line;
-                    to_return += "\n";
-                    this_line = String::new();
-                }
-            }
-            to_return += &this_line;
-            to_return += "\n";
-            to_return = to_return.replace("\n", &(String::from("\n") + &" ".repeat(4)));
-            to_return
-        };
-        output = output.replace("ASCII_DISTRO_LIST", &ascii_distro_list);*/
+        let output = clml(&input).replace("ASCII_DISTRO_LIST", &distro_list(&metas));
         fs::write("./src/assets/.help.clml", output)
             .expect("Failed to write to the file \"./src/assets/.help.clml\"!");
     }
@@ -0,0 +1,570 @@
+use crate::libc;
+use crate::mlua;
+
+use crate::errors;
+use crate::utils::text;
+
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+
+use mlua::prelude::*;
+use once_cell::sync::{Lazy, OnceCell};
+
+use crate::Inject;
+
+/// Whether colors are on, decided once by `init`.
+static ENABLED: OnceCell<bool> = OnceCell::new();
+
+/// The xterm defaults for the 16 named colors, used to pick the closest
+/// one when the terminal can't do better.
+const PALETTE: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// Levels of the 6x6x6 color cube in the 256 color palette.
+const CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// How many colors the terminal can show.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum ColorSupport {
+    /// The 8 basic colors and their bright versions, like the Linux console.
+    Ansi16,
+    Ansi256,
+    TrueColor,
+}
+
+impl ColorSupport {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "16" => Some(ColorSupport::Ansi16),
+            "256" => Some(ColorSupport::Ansi256),
+            "truecolor" => Some(ColorSupport::TrueColor),
+            _ => None,
+        }
+    }
+
+    /// Works out what the terminal supports from `COLORTERM`, `TERM` and
+    /// the terminal's terminfo entry.
+    pub fn detect() -> Self {
+        if matches!(
+            env::var("COLORTERM").as_deref(),
+            Ok("truecolor") | Ok("24bit")
+        ) {
+            return ColorSupport::TrueColor;
+        }
+
+        let term = env::var("TERM").unwrap_or_default();
+        if term.ends_with("-direct") {
+            return ColorSupport::TrueColor;
+        }
+        if term == "linux" || term.starts_with("vt") {
+            return ColorSupport::Ansi16;
+        }
+        match terminfo_colors(&term) {
+            Some(n) if n >= 1 << 24 => ColorSupport::TrueColor,
+            Some(n) if n >= 256 => ColorSupport::Ansi256,
+            Some(_) => ColorSupport::Ansi16,
+            // Without terminfo, assume what freshfetch always used.
+            None if term.contains("256color") || term.is_empty() => ColorSupport::Ansi256,
+            None => ColorSupport::Ansi16,
+        }
+    }
+
+    /// The detected support, worked out once.
+    pub fn current() -> Self {
+        static SUPPORT: Lazy<ColorSupport> = Lazy::new(ColorSupport::detect);
+        *SUPPORT
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            ColorSupport::Ansi16 => "16",
+            ColorSupport::Ansi256 => "256",
+            ColorSupport::TrueColor => "truecolor",
+        }
+    }
+}
+
+/// When to output colors and other escape sequences, from `--color`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ColorMode {
+    /// Only when stdout is a terminal and `NO_COLOR` isn't set.
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorMode {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "auto" => Some(ColorMode::Auto),
+            "always" => Some(ColorMode::Always),
+            "never" => Some(ColorMode::Never),
+            _ => None,
+        }
+    }
+
+    fn enabled(&self) -> bool {
+        match self {
+            ColorMode::Always => true,
+            ColorMode::Never => false,
+            ColorMode::Auto => {
+                // See https://no-color.org: only a non-empty value counts.
+                let no_color = env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+                // SAFETY: `isatty` only looks at the file descriptor.
+                let tty = unsafe { libc::isatty(libc::STDOUT_FILENO) } == 1;
+                !no_color && tty
+            }
+        }
+    }
+}
+
+/// Turns colors on or off for the rest of the run. Only the first call
+/// counts.
+pub(crate) fn init(mode: ColorMode) {
+    let _ = ENABLED.set(mode.enabled());
+}
+
+/// Whether to output colors and other escape sequences.
+pub(crate) fn enabled() -> bool {
+    *ENABLED.get_or_init(|| ColorMode::Auto.enabled())
+}
+
+/// Strips the escape sequences out of `s` when colors are off.
+pub(crate) fn filter(s: &str) -> String {
+    if enabled() {
+        s.to_string()
+    } else {
+        text::strip_ansi(s)
+    }
+}
+
+/// Reads `max_colors` from the compiled terminfo entry for `term`. See
+/// term(5) for the format.
+fn terminfo_colors(term: &str) -> Option<i32> {
+    let first = term.chars().next()?;
+
+    let mut dirs = Vec::new();
+    if let Ok(dir) = env::var("TERMINFO") {
+        dirs.push(PathBuf::from(dir));
+    }
+    if let Ok(home) = env::var("HOME") {
+        dirs.push(PathBuf::from(home).join(".terminfo"));
+    }
+    if let Ok(extra) = env::var("TERMINFO_DIRS") {
+        dirs.extend(
+            extra
+                .split(':')
+                .filter(|d| !d.is_empty())
+                .map(PathBuf::from),
+        );
+    }
+    for dir in ["/etc/terminfo", "/lib/terminfo", "/usr/share/terminfo"] {
+        dirs.push(PathBuf::from(dir));
+    }
+
+    let bytes = dirs.iter().find_map(|dir| {
+        fs::read(dir.join(first.to_string()).join(term))
+            .or_else(|_| fs::read(dir.join(format!("{:x}", first as u32)).join(term)))
+            .ok()
+    })?;
+    max_colors(&bytes)
+}
+
+/// Gets `max_colors` out of a compiled terminfo entry.
+fn max_colors(bytes: &[u8]) -> Option<i32> {
+    let short = |i: usize| -> Option<i32> {
+        let b = bytes.get(i * 2..i * 2 + 2)?;
+        Some(i16::from_le_bytes([b[0], b[1]]) as i32)
+    };
+    // The extended format stores numbers as 32 bits instead of 16.
+    let width = match short(0)? {
+        0o432 => 2,
+        0o1036 => 4,
+        _ => return None,
+    };
+    let (names, bools, numbers) = (short(1)? as usize, short(2)? as usize, short(3)? as usize);
+
+    const MAX_COLORS: usize = 13;
+    if numbers <= MAX_COLORS {
+        return None;
+    }
+    let mut offset = 12 + names + bools;
+    // Numbers are aligned to an even byte.
+    offset += offset % 2;
+    offset += MAX_COLORS * width;
+    let b = bytes.get(offset..offset + width)?;
+    let value = if width == 2 {
+        i16::from_le_bytes([b[0], b[1]]) as i32
+    } else {
+        i32::from_le_bytes([b[0], b[1], b[2], b[3]])
+    };
+    if value < 0 {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Color {
+    /// One of the 16 basic colors, `0` to `15`.
+    Named(u8),
+    /// A color from the 256 color palette.
+    Indexed(u8),
+    Rgb(u8, u8, u8),
+    /// The terminal's default color.
+    Reset,
+}
+
+impl Color {
+    /// Parses `#rrggbb` or `#rgb`.
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let hex = hex.strip_prefix('#').unwrap_or(hex);
+        let channel = |s: &str| u8::from_str_radix(s, 16).ok();
+        match hex.len() {
+            6 => Some(Color::Rgb(
+                channel(&hex[0..2])?,
+                channel(&hex[2..4])?,
+                channel(&hex[4..6])?,
+            )),
+            3 => Some(Color::Rgb(
+                channel(&hex[0..1])? * 17,
+                channel(&hex[1..2])? * 17,
+                channel(&hex[2..3])? * 17,
+            )),
+            _ => None,
+        }
+    }
+
+    /// Gets the foreground color out of SGR parameters like `1;34`,
+    /// `38;5;208` or `38;2;23;147;209`, as used by os-release's
+    /// `ANSI_COLOR`. The last color wins and anything else is ignored.
+    pub fn from_sgr(params: &str) -> Option<Self> {
+        let params = params
+            .split(';')
+            .map(|p| p.trim().parse::<u8>().ok())
+            .collect::<Option<Vec<u8>>>()?;
+
+        let mut to_return = None;
+        let mut i = 0;
+        while i < params.len() {
+            match params[i] {
+                n @ 30..=37 => to_return = Some(Color::Named(n - 30)),
+                n @ 90..=97 => to_return = Some(Color::Named(n - 90 + 8)),
+                38 => match params.get(i + 1) {
+                    Some(5) => {
+                        to_return = Some(Color::Indexed(*params.get(i + 2)?));
+                        i += 2;
+                    }
+                    Some(2) => {
+                        let rgb = params.get(i + 2..i + 5)?;
+                        to_return = Some(Color::Rgb(rgb[0], rgb[1], rgb[2]));
+                        i += 4;
+                    }
+                    _ => return None,
+                },
+                // Backgrounds take arguments too, which mustn't be read as
+                // colors of their own.
+                48 => match params.get(i + 1) {
+                    Some(5) => i += 2,
+                    Some(2) => i += 4,
+                    _ => return None,
+                },
+                _ => (),
+            }
+            i += 1;
+        }
+        to_return
+    }
+
+    /// The SGR sequence to use this as the foreground color, or nothing if
+    /// colors are off.
+    pub fn fg(&self, support: ColorSupport) -> String {
+        self.sgr(support, false)
+    }
+
+    /// The SGR sequence to use this as the background color, or nothing if
+    /// colors are off.
+    pub fn bg(&self, support: ColorSupport) -> String {
+        self.sgr(support, true)
+    }
+
+    fn sgr(&self, support: ColorSupport, background: bool) -> String {
+        if !enabled() {
+            return String::new();
+        }
+        let (base, bright, extended) = if background {
+            (40, 100, 48)
+        } else {
+            (30, 90, 38)
+        };
+        match self.degrade(support) {
+            Color::Named(n) if n < 8 => format!("\u{001b}[{}m", base + n as u32),
+            Color::Named(n) => format!("\u{001b}[{}m", bright + (n as u32 & 7)),
+            Color::Indexed(n) => format!("\u{001b}[{};5;{}m", extended, n),
+            Color::Rgb(r, g, b) => format!("\u{001b}[{};2;{};{};{}m", extended, r, g, b),
+            Color::Reset => format!("\u{001b}[{}m", base + 9),
+        }
+    }
+
+    /// Converts this to the closest color the terminal can show.
+    fn degrade(&self, support: ColorSupport) -> Color {
+        match (*self, support) {
+            (Color::Indexed(n), ColorSupport::Ansi16) if n < 16 => Color::Named(n),
+            (Color::Indexed(n), ColorSupport::Ansi16) => {
+                let (r, g, b) = indexed_rgb(n);
+                Color::Named(nearest_named(r, g, b))
+            }
+            (Color::Rgb(r, g, b), ColorSupport::Ansi16) => Color::Named(nearest_named(r, g, b)),
+            (Color::Rgb(r, g, b), ColorSupport::Ansi256) => {
+                Color::Indexed(nearest_indexed(r, g, b))
+            }
+            (color, _) => color,
+        }
+    }
+}
+
+/// Renders as a foreground color for the detected terminal.
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.fg(ColorSupport::current()))
+    }
+}
+
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> i32 {
+    (r1 as i32 - r2 as i32).pow(2) + (g1 as i32 - g2 as i32).pow(2) + (b1 as i32 - b2 as i32).pow(2)
+}
+
+fn indexed_rgb(n: u8) -> (u8, u8, u8) {
+    match n {
+        0..=15 => PALETTE[n as usize],
+        16..=231 => {
+            let n = n - 16;
+            (
+                CUBE[(n / 36) as usize],
+                CUBE[(n / 6 % 6) as usize],
+                CUBE[(n % 6) as usize],
+            )
+        }
+        _ => {
+            let v = 8 + 10 * (n - 232);
+            (v, v, v)
+        }
+    }
+}
+
+fn nearest_named(r: u8, g: u8, b: u8) -> u8 {
+    (0..16u8)
+        .min_by_key(|i| distance(PALETTE[*i as usize], (r, g, b)))
+        .unwrap_or(7)
+}
+
+/// Maps a color onto the 256 color palette, using the grayscale ramp when
+/// it's closer than the 6x6x6 cube.
+fn nearest_indexed(r: u8, g: u8, b: u8) -> u8 {
+    let level = |v: u8| -> u8 {
+        if v < 48 {
+            0
+        } else if v < 115 {
+            1
+        } else {
+            (v - 35) / 40
+        }
+    };
+    let (lr, lg, lb) = (level(r), level(g), level(b));
+    let cube = (CUBE[lr as usize], CUBE[lg as usize], CUBE[lb as usize]);
+
+    let average = (r as i32 + g as i32 + b as i32) / 3;
+    let gray_index = if average > 238 {
+        23
+    } else {
+        ((average - 3).max(0) / 10) as u8
+    };
+    let gray = 8 + 10 * gray_index;
+
+    if distance((gray, gray, gray), (r, g, b)) < distance(cube, (r, g, b)) {
+        232 + gray_index
+    } else {
+        16 + 36 * lr + 6 * lg + lb
+    }
+}
+
+/// Gets a color from a Lua value: a palette index or a hex string.
+fn from_lua(value: LuaValue) -> LuaResult<Color> {
+    match value {
+        LuaValue::Integer(n) if (0..=255).contains(&n) => Ok(Color::Indexed(n as u8)),
+        LuaValue::Number(n) if (0.0..=255.0).contains(&n) => Ok(Color::Indexed(n as u8)),
+        LuaValue::String(s) => {
+            let s = s.to_str()?;
+            Color::from_hex(s)
+                .ok_or_else(|| LuaError::RuntimeError(format!("\"{}\" isn't a hex color", s)))
+        }
+        _ => Err(LuaError::RuntimeError(String::from(
+            "expected a color index from 0 to 255, or a hex color",
+        ))),
+    }
+}
+
+impl ColorSupport {
+    fn register(&self, lua: &Lua) -> LuaResult<()> {
+        let support = *self;
+        let globals = lua.globals();
+        globals.set("colorSupport", self.as_str())?;
+        globals.set("colorEnabled", enabled())?;
+        globals.set(
+            "rgb",
+            lua.create_function(move |_, (r, g, b): (u8, u8, u8)| {
+                Ok(Color::Rgb(r, g, b).fg(support))
+            })?,
+        )?;
+        globals.set(
+            "hex",
+            lua.create_function(move |_, hex: String| {
+                Color::from_hex(&hex)
+                    .map(|c| c.fg(support))
+                    .ok_or_else(|| LuaError::RuntimeError(format!("\"{}\" isn't a hex color", hex)))
+            })?,
+        )?;
+        globals.set(
+            "fg",
+            lua.create_function(move |_, v: LuaValue| Ok(from_lua(v)?.fg(support)))?,
+        )?;
+        globals.set(
+            "bg",
+            lua.create_function(move |_, v: LuaValue| Ok(from_lua(v)?.bg(support)))?,
+        )?;
+        globals.set(
+            "displayWidth",
+            lua.create_function(|_, s: String| Ok(text::display_width(&s)))?,
+        )?;
+        Ok(())
+    }
+}
+
+/// Adds `colorSupport`, `colorEnabled`, the `rgb`, `hex`, `fg` and `bg`
+/// helpers, and `displayWidth` for lining up text with escapes in it.
+impl Inject for ColorSupport {
+    fn inject(&self, lua: &mut Lua) {
+        match self.register(lua) {
+            Ok(_) => (),
+            Err(e) => {
+                errors::handle(&format!("{}{}", errors::LUA, e));
+                panic!();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A compiled terminfo entry with `max_colors` set to `colors`, or left
+    /// out with `-1`. `width` is 2 for the legacy format and 4 for the
+    /// extended one.
+    fn terminfo(width: usize, names: &str, bools: usize, colors: i32) -> Vec<u8> {
+        let magic: i16 = if width == 2 { 0o432 } else { 0o1036 };
+        let mut bytes = Vec::new();
+        for n in [magic, names.len() as i16 + 1, bools as i16, 14, 0, 0] {
+            bytes.extend(n.to_le_bytes());
+        }
+        bytes.extend(names.as_bytes());
+        bytes.push(0);
+        bytes.extend(std::iter::repeat_n(1, bools));
+        if bytes.len() % 2 == 1 {
+            bytes.push(0);
+        }
+        for i in 0..14 {
+            let n = if i == 13 { colors } else { -1 };
+            bytes.extend(&n.to_le_bytes()[..width]);
+        }
+        bytes
+    }
+
+    #[test]
+    fn reads_legacy_terminfo() {
+        assert_eq!(
+            max_colors(&terminfo(2, "xterm-256color", 37, 256)),
+            Some(256)
+        );
+        // An odd number of name and bool bytes, so there's padding.
+        assert_eq!(max_colors(&terminfo(2, "xterm", 37, 8)), Some(8));
+    }
+
+    #[test]
+    fn reads_extended_terminfo() {
+        let bytes = terminfo(4, "xterm-direct", 38, 0x1000000);
+        assert_eq!(max_colors(&bytes), Some(0x1000000));
+    }
+
+    #[test]
+    fn rejects_bad_terminfo() {
+        assert_eq!(max_colors(&terminfo(2, "dumb", 2, -1)), None);
+        let mut bytes = terminfo(2, "xterm", 37, 8);
+        bytes[0] = 0;
+        assert_eq!(max_colors(&bytes), None);
+        let bytes = terminfo(2, "xterm", 37, 8);
+        assert_eq!(max_colors(&bytes[..bytes.len() - 1]), None);
+        assert_eq!(max_colors(&[]), None);
+    }
+
+    #[test]
+    fn parses_hex() {
+        assert_eq!(
+            Color::from_hex("#1793d1"),
+            Some(Color::Rgb(0x17, 0x93, 0xd1))
+        );
+        assert_eq!(Color::from_hex("f80"), Some(Color::Rgb(0xff, 0x88, 0x00)));
+        assert_eq!(Color::from_hex("#12345"), None);
+        assert_eq!(Color::from_hex("#zzzzzz"), None);
+    }
+
+    #[test]
+    fn parses_os_release_colors() {
+        assert_eq!(Color::from_sgr("1;34"), Some(Color::Named(4)));
+        assert_eq!(Color::from_sgr("0;94"), Some(Color::Named(12)));
+        assert_eq!(Color::from_sgr("38;5;208"), Some(Color::Indexed(208)));
+        assert_eq!(
+            Color::from_sgr("38;2;23;147;209"),
+            Some(Color::Rgb(23, 147, 209))
+        );
+        // The background's arguments aren't colors.
+        assert_eq!(Color::from_sgr("48;5;31;1"), None);
+        assert_eq!(Color::from_sgr("48;2;1;2;3;32"), Some(Color::Named(2)));
+        assert_eq!(Color::from_sgr("38;5"), None);
+        assert_eq!(Color::from_sgr("blue"), None);
+    }
+
+    #[test]
+    fn degrades_to_what_the_terminal_supports() {
+        let orange = Color::Rgb(255, 135, 0);
+        assert_eq!(orange.degrade(ColorSupport::TrueColor), orange);
+        assert_eq!(orange.degrade(ColorSupport::Ansi256), Color::Indexed(208));
+        assert_eq!(
+            Color::Indexed(9).degrade(ColorSupport::Ansi16),
+            Color::Named(9)
+        );
+        assert_eq!(
+            Color::Rgb(250, 250, 250).degrade(ColorSupport::Ansi16),
+            Color::Named(15)
+        );
+    }
+}
@@ -0,0 +1,55 @@
+use crate::{errors, mlua};
+
+use std::env::var;
+use std::fs::read_to_string;
+use std::path::Path;
+
+use mlua::prelude::*;
+
+/// Settings from `~/.config/freshfetch/config.lua`, for what has to be
+/// decided before the other scripts run. It's Lua that sets globals, like
+/// `osColors = true`. Each one is also a flag, and setting either is enough.
+#[derive(Default)]
+pub(crate) struct Config {
+    /// Like `--os-colors`.
+    pub os_colors: bool,
+}
+
+impl Config {
+    pub fn load() -> Self {
+        let path = Path::new("/home/")
+            .join(var("USER").unwrap_or_default())
+            .join(".config/freshfetch/config.lua");
+        if !path.exists() {
+            return Config::default();
+        }
+
+        let script = match read_to_string(&path) {
+            Ok(v) => v,
+            Err(e) => {
+                errors::handle(&format!(
+                    "{}{file}{}{err}",
+                    errors::io::READ.0,
+                    errors::io::READ.1,
+                    file = path.to_string_lossy(),
+                    err = e
+                ));
+                panic!();
+            }
+        };
+        let lua = Lua::new();
+        let os_colors = lua
+            .load(&script)
+            .exec()
+            .and_then(|_| lua.globals().get::<_, Option<bool>>("osColors"));
+        match os_colors {
+            Ok(os_colors) => Config {
+                os_colors: os_colors.unwrap_or(false),
+            },
+            Err(e) => {
+                errors::handle(&format!("{}{}", errors::LUA, e));
+                panic!();
+            }
+        }
+    }
+}
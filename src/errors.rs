@@ -1,3 +1,5 @@
+use crate::color;
+
 use std::process::exit;
 
 pub(crate) static LUA: &str = "A Lua error occurred. Details:\n";
@@ -7,6 +9,10 @@ pub(crate) static PARSE: (&str, &str, &str) = (
     "\" into a \"",
     "\". Details:\n",
 );
+pub(crate) static PRESET: (&str, &str) = (
+    "There's no preset called \"",
+    "\". Run \"freshfetch presets list\" to see them.",
+);
 
 pub(crate) mod io {
     pub(crate) static READ: (&str, &str) = (
@@ -16,6 +22,7 @@ pub(crate) mod io {
 }
 
 pub(crate) fn handle(msg: &str) {
-    println!("\u{001b}[38;5;1mError.\u{001b}[0m\n{msg}",);
+    let error = color::filter("\u{001b}[38;5;1mError.\u{001b}[0m");
+    println!("{error}\n{msg}");
     exit(1);
 }
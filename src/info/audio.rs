@@ -0,0 +1,185 @@
+use crate::mlua;
+
+use super::kernel;
+use super::utils::{Grep, PsAux};
+use crate::errors;
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use mlua::prelude::*;
+
+use crate::Inject;
+use kernel::Kernel;
+
+#[derive(Clone, Debug)]
+pub(crate) struct SoundCard {
+    pub index: u32,
+    pub id: String,
+    pub driver: String,
+    pub name: String,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct Audio {
+    pub server: Option<String>,
+    pub cards: Vec<SoundCard>,
+}
+
+impl Audio {
+    pub fn new(k: &Kernel) -> Option<Self> {
+        if k.name != "Linux" {
+            return None;
+        }
+
+        let cards = fs::read_to_string("/proc/asound/cards")
+            .map(|v| Self::parse_cards(&v))
+            .unwrap_or_default();
+        let server = Self::server(!cards.is_empty());
+
+        if server.is_none() && cards.is_empty() {
+            None
+        } else {
+            Some(Audio { server, cards })
+        }
+    }
+
+    fn server(has_alsa: bool) -> Option<String> {
+        let ps = PsAux::new();
+        let running = |name: &str| {
+            !ps.grep(Grep {
+                max: Some(1),
+                search: Some(String::from(name)),
+                searches: None,
+                only_matching: Some(true),
+            })
+            .is_empty()
+        };
+        let runtime_dir = env::var("XDG_RUNTIME_DIR").ok();
+        let socket = |name: &str| {
+            runtime_dir
+                .as_ref()
+                .map(|d| Path::new(d).join(name).exists())
+                .unwrap_or(false)
+        };
+
+        // pipewire-pulse also creates `pulse/native`, so PipeWire has to be
+        // checked before PulseAudio.
+        if running("pipewire") || socket("pipewire-0") {
+            let mut name = String::from("PipeWire");
+            if running("pipewire-pulse") {
+                name += " (PulseAudio)";
+            }
+            Some(name)
+        } else if running("pulseaudio") || socket("pulse/native") {
+            Some(String::from("PulseAudio"))
+        } else if running("jackd") || running("jackdbus") {
+            Some(String::from("JACK"))
+        } else if has_alsa || Path::new("/proc/asound").is_dir() {
+            Some(String::from("ALSA"))
+        } else {
+            None
+        }
+    }
+
+    /// Parses `/proc/asound/cards`, which lists each card on two lines:
+    ///
+    /// ```text
+    ///  0 [PCH            ]: HDA-Intel - HDA Intel PCH
+    ///                       HDA Intel PCH at 0xf7f10000 irq 32
+    /// ```
+    fn parse_cards(cards: &str) -> Vec<SoundCard> {
+        let mut to_return = Vec::new();
+        for line in cards.lines() {
+            let trimmed = line.trim_start();
+            let Some((index, rest)) = trimmed.split_once(' ') else {
+                continue;
+            };
+            let Ok(index) = index.parse::<u32>() else {
+                continue;
+            };
+            let Some((id, rest)) = rest
+                .trim_start()
+                .strip_prefix('[')
+                .and_then(|r| r.split_once("]:"))
+            else {
+                continue;
+            };
+            let (driver, name) = rest.split_once(" - ").unwrap_or((rest, ""));
+            to_return.push(SoundCard {
+                index,
+                id: id.trim().to_string(),
+                driver: driver.trim().to_string(),
+                name: name.trim().to_string(),
+            });
+        }
+        to_return
+    }
+
+    fn table<'lua>(&self, lua: &'lua Lua) -> LuaResult<LuaTable<'lua>> {
+        let t = lua.create_table()?;
+        t.set("server", self.server.as_deref())?;
+        let cards = lua.create_table()?;
+        for (i, card) in self.cards.iter().enumerate() {
+            let c = lua.create_table()?;
+            c.set("index", card.index)?;
+            c.set("id", card.id.as_str())?;
+            c.set("driver", card.driver.as_str())?;
+            c.set("name", card.name.as_str())?;
+            cards.raw_set((i + 1) as i64, c)?;
+        }
+        t.set("cards", cards)?;
+        Ok(t)
+    }
+}
+
+impl Inject for Audio {
+    fn inject(&self, lua: &mut Lua) {
+        match self.table(lua).and_then(|t| lua.globals().set("audio", t)) {
+            Ok(_) => (),
+            Err(e) => {
+                errors::handle(&format!("{}{}", errors::LUA, e));
+                panic!();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_cards() {
+        let cards = Audio::parse_cards(
+            " 0 [PCH            ]: HDA-Intel - HDA Intel PCH\n\
+             \x20                     HDA Intel PCH at 0x6004118000 irq 177\n\
+             \x201 [NVidia         ]: HDA-Intel - HDA NVidia\n\
+             \x20                     HDA NVidia at 0x8e080000 irq 17\n\
+             10 [Headset        ]: USB-Audio - Arctis 7\n\
+             \x20                     SteelSeries Arctis 7 at usb-0000:00:14.0-2, full speed\n\
+             \x202 [Loopback       ]: Loopback\n\
+             \x20                     Loopback 1\n",
+        );
+        let cards = cards
+            .iter()
+            .map(|c| (c.index, c.id.as_str(), c.driver.as_str(), c.name.as_str()))
+            .collect::<Vec<(u32, &str, &str, &str)>>();
+        assert_eq!(
+            cards,
+            [
+                (0, "PCH", "HDA-Intel", "HDA Intel PCH"),
+                (1, "NVidia", "HDA-Intel", "HDA NVidia"),
+                (10, "Headset", "USB-Audio", "Arctis 7"),
+                (2, "Loopback", "Loopback", ""),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_no_cards() {
+        assert!(Audio::parse_cards("--- no soundcards ---\n").is_empty());
+        assert!(Audio::parse_cards("").is_empty());
+    }
+}
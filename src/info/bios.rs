@@ -0,0 +1,253 @@
+use super::kernel;
+use super::utils::read_trimmed;
+use crate::{errors, mlua, Inject};
+use kernel::Kernel;
+use mlua::prelude::*;
+use std::path::Path;
+
+#[derive(Clone, Debug)]
+pub(crate) struct Chassis {
+    pub code: u8,
+    pub name: &'static str,
+    pub form_factor: &'static str,
+}
+
+impl Chassis {
+    /// Decodes an SMBIOS chassis type (DMI table 3, offset 05h).
+    pub fn from_code(code: u8) -> Self {
+        let (name, form_factor) = match code {
+            1 => ("Other", "unknown"),
+            3 => ("Desktop", "desktop"),
+            4 => ("Low Profile Desktop", "desktop"),
+            5 => ("Pizza Box", "desktop"),
+            6 => ("Mini Tower", "desktop"),
+            7 => ("Tower", "desktop"),
+            8 => ("Portable", "laptop"),
+            9 => ("Laptop", "laptop"),
+            10 => ("Notebook", "laptop"),
+            11 => ("Hand Held", "handheld"),
+            12 => ("Docking Station", "laptop"),
+            13 => ("All in One", "desktop"),
+            14 => ("Sub Notebook", "laptop"),
+            15 => ("Space-saving", "desktop"),
+            16 => ("Lunch Box", "desktop"),
+            17 => ("Main Server Chassis", "server"),
+            18 => ("Expansion Chassis", "server"),
+            19 => ("SubChassis", "server"),
+            20 => ("Bus Expansion Chassis", "server"),
+            21 => ("Peripheral Chassis", "server"),
+            22 => ("RAID Chassis", "server"),
+            23 => ("Rack Mount", "server"),
+            24 => ("Sealed-case PC", "desktop"),
+            25 => ("Multi-system Chassis", "server"),
+            26 => ("Compact PCI", "server"),
+            27 => ("Advanced TCA", "server"),
+            28 => ("Blade", "server"),
+            29 => ("Blade Enclosure", "server"),
+            30 => ("Tablet", "tablet"),
+            31 => ("Convertible", "laptop"),
+            32 => ("Detachable", "tablet"),
+            33 => ("IoT Gateway", "embedded"),
+            34 => ("Embedded PC", "embedded"),
+            35 => ("Mini PC", "desktop"),
+            36 => ("Stick PC", "desktop"),
+            _ => ("Unknown", "unknown"),
+        };
+        Chassis {
+            code,
+            name,
+            form_factor,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct Bios {
+    pub vendor: String,
+    pub version: String,
+    pub date: String,
+    pub release: String,
+    pub ec_release: String,
+    pub chassis: Option<Chassis>,
+}
+
+impl Bios {
+    pub fn new(k: &Kernel) -> Option<Self> {
+        if k.name != "Linux" {
+            return None;
+        }
+
+        Self::from_sysfs(Path::new("/sys/class/dmi/id"))
+    }
+
+    /// Reads the firmware info in `dmi`, which is normally
+    /// `/sys/class/dmi/id`.
+    pub fn from_sysfs(dmi: &Path) -> Option<Self> {
+        if !dmi.is_dir() {
+            return None;
+        }
+        let field = |name: &str| read_trimmed(dmi.join(name)).unwrap_or_default();
+
+        let chassis = read_trimmed(dmi.join("chassis_type"))
+            .and_then(|v| v.parse::<u8>().ok())
+            .map(Chassis::from_code);
+
+        let bios = Bios {
+            vendor: field("bios_vendor"),
+            version: field("bios_version"),
+            date: field("bios_date"),
+            release: field("bios_release"),
+            ec_release: field("ec_firmware_release"),
+            chassis,
+        };
+
+        if bios.vendor.is_empty() && bios.version.is_empty() && bios.chassis.is_none() {
+            None
+        } else {
+            Some(bios)
+        }
+    }
+}
+
+impl Inject for Bios {
+    fn inject(&self, lua: &mut Lua) {
+        match lua.create_table() {
+            Ok(t) => {
+                for (key, value) in [
+                    ("vendor", &self.vendor),
+                    ("version", &self.version),
+                    ("date", &self.date),
+                    ("release", &self.release),
+                    ("ecRelease", &self.ec_release),
+                ] {
+                    if let Err(e) = t.set(key, value.as_str()) {
+                        errors::handle(&format!("{}{}", errors::LUA, e));
+                        panic!();
+                    }
+                }
+                if let Some(chassis) = &self.chassis {
+                    match lua.create_table() {
+                        Ok(c) => {
+                            if c.set("code", chassis.code).is_err()
+                                || c.set("name", chassis.name).is_err()
+                                || c.set("formFactor", chassis.form_factor).is_err()
+                                || t.set("chassis", c).is_err()
+                            {
+                                errors::handle("Failed to inject chassis info to Lua");
+                                panic!();
+                            }
+                        }
+                        Err(e) => {
+                            errors::handle(&format!("{}{}", errors::LUA, e));
+                            panic!();
+                        }
+                    }
+                }
+                if let Err(e) = lua.globals().set("bios", t) {
+                    errors::handle(&format!("{}{}", errors::LUA, e));
+                    panic!();
+                }
+            }
+            Err(e) => {
+                errors::handle(&format!("{}{}", errors::LUA, e));
+                panic!();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+    use std::path::PathBuf;
+
+    #[test]
+    fn decodes_chassis_types() {
+        for (code, name, form_factor) in [
+            (3, "Desktop", "desktop"),
+            (9, "Laptop", "laptop"),
+            (10, "Notebook", "laptop"),
+            (17, "Main Server Chassis", "server"),
+            (23, "Rack Mount", "server"),
+            (30, "Tablet", "tablet"),
+            (31, "Convertible", "laptop"),
+            (35, "Mini PC", "desktop"),
+            (36, "Stick PC", "desktop"),
+            // SMBIOS says 2 is "Unknown", and 0 and anything past 36 aren't
+            // defined.
+            (0, "Unknown", "unknown"),
+            (2, "Unknown", "unknown"),
+            (37, "Unknown", "unknown"),
+            (255, "Unknown", "unknown"),
+        ] {
+            let chassis = Chassis::from_code(code);
+            assert_eq!(chassis.code, code);
+            assert_eq!(
+                (chassis.name, chassis.form_factor),
+                (name, form_factor),
+                "{}",
+                code
+            );
+        }
+    }
+
+    /// A made up `/sys/class/dmi/id` in a temp dir, removed when it's
+    /// dropped.
+    struct FakeDmi(PathBuf);
+
+    impl FakeDmi {
+        fn new(name: &str) -> Self {
+            let root = std::env::temp_dir().join(format!(
+                "freshfetch-bios-{}-{}",
+                name,
+                std::process::id()
+            ));
+            let _ = fs::remove_dir_all(&root);
+            fs::create_dir_all(&root).unwrap();
+            FakeDmi(root)
+        }
+
+        fn file(&self, name: &str, contents: &str) -> &Self {
+            fs::write(self.0.join(name), contents).unwrap();
+            self
+        }
+    }
+
+    impl Drop for FakeDmi {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn reads_dmi() {
+        let dmi = FakeDmi::new("laptop");
+        dmi.file("bios_vendor", "LENOVO\n")
+            .file("bios_version", "N3AET75W (1.40 )\n")
+            .file("bios_date", "05/17/2023\n")
+            .file("bios_release", "1.40\n")
+            .file("chassis_type", "10\n");
+        let bios = Bios::from_sysfs(&dmi.0).unwrap();
+        assert_eq!(bios.vendor, "LENOVO");
+        assert_eq!(bios.version, "N3AET75W (1.40 )");
+        assert_eq!(bios.date, "05/17/2023");
+        assert_eq!(bios.release, "1.40");
+        assert_eq!(bios.ec_release, "");
+        assert_eq!(bios.chassis.map(|c| c.name), Some("Notebook"));
+    }
+
+    #[test]
+    fn skips_bad_or_missing_dmi() {
+        let dmi = FakeDmi::new("bad-chassis");
+        dmi.file("bios_vendor", "SeaBIOS\n")
+            .file("chassis_type", "300\n");
+        let bios = Bios::from_sysfs(&dmi.0).unwrap();
+        assert!(bios.chassis.is_none());
+
+        let dmi = FakeDmi::new("empty");
+        assert!(Bios::from_sysfs(&dmi.0).is_none());
+        assert!(Bios::from_sysfs(&dmi.0.join("missing")).is_none());
+    }
+}
@@ -1,33 +1,154 @@
+use crate::chrono;
 use crate::mlua;
+use crate::uname;
+use crate::users;
 
+use super::utils::read_trimmed;
 use crate::errors;
 
 use mlua::prelude::*;
 
 use crate::{ Inject };
 
-use std::fs::{ read_to_string };
 use std::env::{ var };
+use std::fs::{ self, read_to_string };
+use std::path::{ Path };
+
+use chrono::{ Local, TimeZone };
+use uname::uname;
+
+/// Size of a glibc `struct utmp` on Linux. The layout is the same on 32 and
+/// 64 bit targets, since `ut_tv` is always a pair of `int32_t`s.
+const UTMP_SIZE: usize = 384;
+const UTMP_USER_PROCESS: i16 = 7;
+
+#[derive(Clone, Debug)]
+struct UtmpEntry {
+	pid: i32,
+	line: String,
+	user: String,
+	time: i64,
+}
 
 #[derive(Clone, Debug)]
 pub(crate) struct Context {
 	pub user: String,
 	pub host: String,
+	pub real_name: String,
+	pub uid: u32,
+	pub gid: u32,
+	pub groups: Vec<String>,
+	pub login_time: Option<String>,
+	pub sessions: usize,
+	pub ssh: bool,
+	pub ssh_client: Option<String>,
 }
 
 impl Context {
 	pub fn new() -> Option<Self> {
-		Some(Context {
-			user: match var("USER") {
+		let uid = users::get_current_uid();
+		let gid = users::get_current_gid();
+		let user = match users::get_user_by_uid(uid) {
+			Some(u) => u.name().to_string_lossy().to_string(),
+			None => match var("USER") {
 				Ok(v) => v,
 				Err(_) => return None,
 			},
-			host: match read_to_string("/etc/hostname") {
-				Ok(v) => v,
-				Err(_) => return None,
-			}
+		};
+
+		let host = match uname() {
+			Ok(info) if !info.nodename.is_empty() => info.nodename,
+			_ => read_trimmed("/etc/hostname").unwrap_or_default(),
+		};
+
+		let groups = users::get_user_groups(&user, gid)
+			.unwrap_or_default()
+			.iter()
+			.map(|g| g.name().to_string_lossy().to_string())
+			.collect();
+
+		let utmp = Self::read_utmp();
+		let tty = Self::tty();
+		let login_time = utmp.iter()
+			.filter(|e| e.user == user)
+			.find(|e| tty.as_deref() == Some(e.line.as_str()))
+			.or_else(|| utmp.iter()
+				.filter(|e| e.user == user)
+				.min_by_key(|e| e.time))
+			.and_then(|e| Local.timestamp_opt(e.time, 0).single())
+			.map(|t| t.format("%Y-%m-%d %H:%M").to_string());
+
+		let ssh_connection = var("SSH_CONNECTION").or_else(|_| var("SSH_CLIENT")).ok();
+
+		Some(Context {
+			real_name: Self::real_name(&user),
+			user,
+			host,
+			uid,
+			gid,
+			groups,
+			login_time,
+			sessions: utmp.len(),
+			ssh: ssh_connection.is_some(),
+			ssh_client: ssh_connection
+				.and_then(|v| v.split_whitespace().next().map(String::from)),
 		})
-	} 
+	}
+
+	/// Reads the first field of the GECOS entry for `user` from
+	/// `/etc/passwd`.
+	fn real_name(user: &str) -> String {
+		read_to_string("/etc/passwd")
+			.unwrap_or_default()
+			.lines()
+			.map(|l| l.split(':').collect::<Vec<&str>>())
+			.find(|f| f.len() >= 5 && f[0] == user)
+			.and_then(|f| f[4].split(',').next().map(|n| n.trim().to_string()))
+			.unwrap_or_default()
+	}
+
+	/// Returns the controlling terminal as it appears in utmp's `ut_line`,
+	/// e.g. `pts/3`.
+	fn tty() -> Option<String> {
+		fs::read_link("/proc/self/fd/0").ok()
+			.and_then(|p| p.strip_prefix("/dev/").ok().map(|p| p.to_string_lossy().to_string()))
+	}
+
+	/// Returns the live login sessions recorded in utmp.
+	fn read_utmp() -> Vec<UtmpEntry> {
+		let raw = match fs::read("/run/utmp").or_else(|_| fs::read("/var/run/utmp")) {
+			Ok(v) => v,
+			Err(_) => return Vec::new(),
+		};
+		Self::parse_utmp(&raw)
+			.into_iter()
+			// utmp isn't always cleaned up after a crash, so skip sessions
+			// whose process is gone.
+			.filter(|e| Path::new("/proc").join(e.pid.to_string()).exists())
+			.collect()
+	}
+
+	/// Returns the user processes in the raw contents of a utmp file.
+	fn parse_utmp(raw: &[u8]) -> Vec<UtmpEntry> {
+		let field = |bytes: &[u8]| -> String {
+			let end = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+			String::from_utf8_lossy(&bytes[..end]).to_string()
+		};
+		let int = |bytes: &[u8]| -> i32 {
+			i32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+		};
+
+		raw.chunks_exact(UTMP_SIZE)
+			.filter(|r| i16::from_ne_bytes([r[0], r[1]]) == UTMP_USER_PROCESS)
+			.map(|r| UtmpEntry {
+				pid: int(&r[4..8]),
+				line: field(&r[8..40]),
+				user: field(&r[44..76]),
+				time: int(&r[340..344]) as i64,
+			})
+			.filter(|e| !e.user.is_empty())
+			.collect()
+	}
 }
 
 impl Inject for Context {
@@ -43,6 +164,38 @@ impl Inject for Context {
 					Ok(_) => (),
 					Err(e) => { errors::handle(&format!("{}{}", errors::LUA, e)); panic!() }
 				}
+				match t.set("realName", self.real_name.as_str()) {
+					Ok(_) => (),
+					Err(e) => { errors::handle(&format!("{}{}", errors::LUA, e)); panic!() }
+				}
+				match t.set("uid", self.uid) {
+					Ok(_) => (),
+					Err(e) => { errors::handle(&format!("{}{}", errors::LUA, e)); panic!() }
+				}
+				match t.set("gid", self.gid) {
+					Ok(_) => (),
+					Err(e) => { errors::handle(&format!("{}{}", errors::LUA, e)); panic!() }
+				}
+				match t.set("groups", self.groups.clone()) {
+					Ok(_) => (),
+					Err(e) => { errors::handle(&format!("{}{}", errors::LUA, e)); panic!() }
+				}
+				match t.set("loginTime", self.login_time.as_deref()) {
+					Ok(_) => (),
+					Err(e) => { errors::handle(&format!("{}{}", errors::LUA, e)); panic!() }
+				}
+				match t.set("sessions", self.sessions) {
+					Ok(_) => (),
+					Err(e) => { errors::handle(&format!("{}{}", errors::LUA, e)); panic!() }
+				}
+				match t.set("ssh", self.ssh) {
+					Ok(_) => (),
+					Err(e) => { errors::handle(&format!("{}{}", errors::LUA, e)); panic!() }
+				}
+				match t.set("sshClient", self.ssh_client.as_deref()) {
+					Ok(_) => (),
+					Err(e) => { errors::handle(&format!("{}{}", errors::LUA, e)); panic!() }
+				}
 				match globals.set("context", t) {
 					Ok(_) => (),
 					Err(e) => { errors::handle(&format!("{}{}", errors::LUA, e)); panic!() }
@@ -52,3 +205,49 @@ impl Inject for Context {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Builds a utmp record like glibc writes them.
+	fn record(kind: i16, pid: i32, line: &str, user: &str, time: i32) -> Vec<u8> {
+		let mut r = vec![0u8; UTMP_SIZE];
+		r[0..2].copy_from_slice(&kind.to_ne_bytes());
+		r[4..8].copy_from_slice(&pid.to_ne_bytes());
+		r[8..8 + line.len()].copy_from_slice(line.as_bytes());
+		r[44..44 + user.len()].copy_from_slice(user.as_bytes());
+		r[340..344].copy_from_slice(&time.to_ne_bytes());
+		r
+	}
+
+	#[test]
+	fn parses_utmp() {
+		let mut raw = Vec::new();
+		// A boot record, a user session, a dead one and a login with no user yet.
+		raw.extend(record(2, 0, "~", "reboot", 1));
+		raw.extend(record(UTMP_USER_PROCESS, 1234, "pts/3", "alice", 1_700_000_000));
+		raw.extend(record(8, 1300, "pts/4", "", 2));
+		raw.extend(record(UTMP_USER_PROCESS, 1400, "tty1", "", 3));
+		// A record that was cut off partway through.
+		raw.extend(&record(UTMP_USER_PROCESS, 1500, "tty2", "bob", 4)[..100]);
+
+		let entries = Context::parse_utmp(&raw);
+		assert_eq!(entries.len(), 1);
+		assert_eq!(entries[0].pid, 1234);
+		assert_eq!(entries[0].line, "pts/3");
+		assert_eq!(entries[0].user, "alice");
+		assert_eq!(entries[0].time, 1_700_000_000);
+	}
+
+	#[test]
+	fn reads_full_width_fields() {
+		// ut_line and ut_user aren't NUL-terminated when they're full.
+		let line = "x".repeat(32);
+		let user = "u".repeat(32);
+		let raw = record(UTMP_USER_PROCESS, 1, &line, &user, 0);
+		let entries = Context::parse_utmp(&raw);
+		assert_eq!(entries[0].line, line);
+		assert_eq!(entries[0].user, user);
+	}
+}
@@ -2,6 +2,7 @@ use crate::cmd_lib;
 use crate::mlua;
 
 use super::kernel;
+use super::utils::parse_env_file;
 use crate::errors;
 
 use std::env;
@@ -12,6 +13,8 @@ use std::process::{Command, Stdio};
 use cmd_lib::run_fun;
 use mlua::prelude::*;
 
+use crate::assets::ascii_art::ArtEntry;
+use crate::color::Color;
 use crate::Inject;
 use kernel::Kernel;
 
@@ -19,6 +22,14 @@ use kernel::Kernel;
 pub(crate) struct Distro {
     pub long_name: String,
     pub short_name: String,
+    /// os-release `NAME`, like `Ubuntu`.
+    pub os_name: Option<String>,
+    /// os-release `ID`, like `ubuntu`.
+    pub id: Option<String>,
+    /// os-release `ID_LIKE`, like `["ubuntu", "debian"]`, closest first.
+    pub id_like: Vec<String>,
+    /// os-release `ANSI_COLOR`, the color the distro wants its name shown in.
+    pub ansi_color: Option<Color>,
     pub architecture: String,
     pub colors: DistroColors,
 }
@@ -86,9 +97,26 @@ impl Distro {
             }
             _ => {} // Do nothing, unknown OS'es should have already exited by now.
         }
+        let os_release = ["/etc/os-release", "/usr/lib/os-release"]
+            .iter()
+            .find_map(|path| fs::read_to_string(path).ok())
+            .map(|v| parse_env_file(&v))
+            .unwrap_or_default();
         Distro {
             long_name,
             short_name,
+            os_name: os_release.get("NAME").filter(|v| !v.is_empty()).cloned(),
+            id: os_release
+                .get("ID")
+                .filter(|v| !v.is_empty())
+                .map(|v| v.to_lowercase()),
+            id_like: os_release
+                .get("ID_LIKE")
+                .map(|v| v.split_whitespace().map(|v| v.to_lowercase()).collect())
+                .unwrap_or_default(),
+            ansi_color: os_release
+                .get("ANSI_COLOR")
+                .and_then(|v| Color::from_sgr(v)),
             architecture: k.architecture.clone(),
             colors: DistroColors::new(),
         }
@@ -113,6 +141,18 @@ impl Inject for Distro {
                     Ok(_) => (),
                     Err(e) => errors::handle(&format!("{}{}", errors::LUA, e)),
                 }
+                match t.set("id", self.id.as_deref()) {
+                    Ok(_) => (),
+                    Err(e) => errors::handle(&format!("{}{}", errors::LUA, e)),
+                }
+                match t.set("idLike", self.id_like.clone()) {
+                    Ok(_) => (),
+                    Err(e) => errors::handle(&format!("{}{}", errors::LUA, e)),
+                }
+                match t.set("ansiColor", self.ansi_color.map(|c| c.to_string())) {
+                    Ok(_) => (),
+                    Err(e) => errors::handle(&format!("{}{}", errors::LUA, e)),
+                }
                 match globals.set("distro", t) {
                     Ok(_) => (),
                     Err(e) => errors::handle(&format!("{}{}", errors::LUA, e)),
@@ -123,18 +163,33 @@ impl Inject for Distro {
     }
 }
 
+/// The colors of the distro's art. They're rendered for the terminal's
+/// color support when they're given to Lua.
 #[derive(Clone, Debug)]
-pub(crate) struct DistroColors(pub String, pub String, pub String, pub String);
+pub(crate) struct DistroColors(pub Color, pub Color, pub Color, pub Color);
 
 impl DistroColors {
     pub fn new() -> Self {
         DistroColors(
-            String::from("\u{001b}[38;5;7m"),
-            String::from("\u{001b}[38;5;7m"),
-            String::from("\u{001b}[38;5;7m"),
-            String::from("\u{001b}[38;5;7m"),
+            Color::Indexed(7),
+            Color::Indexed(7),
+            Color::Indexed(7),
+            Color::Indexed(7),
         )
     }
+
+    /// Like `from`, but with `accent` in the slots `colors` leaves empty. Art
+    /// that was never given colors counts as having none. With `prefer`,
+    /// `accent` is used for every slot.
+    pub fn with_accent(colors: [Option<Color>; 4], accent: Option<Color>, prefer: bool) -> Self {
+        match accent {
+            Some(accent) if prefer || colors == ArtEntry::DEFAULT_COLORS => {
+                DistroColors::from([Some(accent), None, None, None])
+            }
+            Some(accent) => DistroColors::from(colors.map(|c| c.or(Some(accent)))),
+            None => DistroColors::from(colors),
+        }
+    }
 }
 
 impl Inject for DistroColors {
@@ -143,32 +198,13 @@ impl Inject for DistroColors {
 
         match lua.create_table() {
             Ok(t) => {
-                match t.raw_insert(1, self.0.as_str()) {
-                    Ok(_) => (),
-                    Err(e) => {
-                        errors::handle(&format!("{}{}", errors::LUA, e));
-                        panic!();
-                    }
-                }
-                match t.raw_insert(2, self.1.as_str()) {
-                    Ok(_) => (),
-                    Err(e) => {
-                        errors::handle(&format!("{}{}", errors::LUA, e));
-                        panic!();
-                    }
-                }
-                match t.raw_insert(3, self.2.as_str()) {
-                    Ok(_) => (),
-                    Err(e) => {
-                        errors::handle(&format!("{}{}", errors::LUA, e));
-                        panic!();
-                    }
-                }
-                match t.raw_insert(4, self.3.as_str()) {
-                    Ok(_) => (),
-                    Err(e) => {
-                        errors::handle(&format!("{}{}", errors::LUA, e));
-                        panic!();
+                for (i, color) in [&self.0, &self.1, &self.2, &self.3].iter().enumerate() {
+                    match t.raw_insert(i as i64 + 1, color.to_string()) {
+                        Ok(_) => (),
+                        Err(e) => {
+                            errors::handle(&format!("{}{}", errors::LUA, e));
+                            panic!();
+                        }
                     }
                 }
                 match globals.set("distroColors", t) {
@@ -187,17 +223,15 @@ impl Inject for DistroColors {
     }
 }
 
-impl From<[Option<&'static str>; 4]> for DistroColors {
-    fn from(v: [Option<&'static str>; 4]) -> Self {
-        let one = String::from(v[0].unwrap_or("white"));
-        let mut two = String::from(v[1].unwrap_or(v[0].unwrap_or("\u{001b}[38;5;7m")));
-        let three =
-            String::from(v[2].unwrap_or(v[1].unwrap_or(v[0].unwrap_or("\u{001b}[38;5;7m"))));
-        let four = String::from(
-            v[3].unwrap_or(v[2].unwrap_or(v[1].unwrap_or(v[0].unwrap_or("\u{001b}[38;5;7m")))),
-        );
-        if two == "\u{001b}[38;5;7m" {
-            two = one.clone();
+impl From<[Option<Color>; 4]> for DistroColors {
+    fn from(v: [Option<Color>; 4]) -> Self {
+        let white = Color::Indexed(7);
+        let one = v[0].unwrap_or(white);
+        let mut two = v[1].unwrap_or(one);
+        let three = v[2].unwrap_or(two);
+        let four = v[3].unwrap_or(three);
+        if two == white {
+            two = one;
         }
         DistroColors(one, two, three, four)
     }
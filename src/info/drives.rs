@@ -0,0 +1,383 @@
+use crate::mlua;
+
+use super::kernel;
+use super::utils::read_trimmed;
+use crate::errors;
+
+use std::cmp::Ordering;
+use std::fs;
+use std::path::Path;
+
+use mlua::prelude::*;
+
+use crate::Inject;
+use kernel::Kernel;
+
+#[derive(Clone, Debug)]
+pub(crate) struct Partition {
+    pub name: String,
+    /// Size in bytes.
+    pub size: u64,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct Drive {
+    pub name: String,
+    pub model: String,
+    pub vendor: String,
+    /// Size in bytes.
+    pub size: u64,
+    pub rotational: bool,
+    pub removable: bool,
+    pub transport: &'static str,
+    pub partitions: Vec<Partition>,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct Drives(pub Vec<Drive>);
+
+impl Drives {
+    pub fn new(k: &Kernel) -> Option<Self> {
+        if k.name != "Linux" {
+            return None;
+        }
+        let drives = Self::from_sysfs(Path::new("/sys/block"));
+        if drives.0.is_empty() {
+            None
+        } else {
+            Some(drives)
+        }
+    }
+
+    /// Lists the block devices in `root`, which is normally `/sys/block`.
+    pub fn from_sysfs(root: &Path) -> Self {
+        let mut names = fs::read_dir(root)
+            .map(|d| {
+                d.flatten()
+                    .map(|e| e.file_name().to_string_lossy().to_string())
+                    .collect::<Vec<String>>()
+            })
+            .unwrap_or_default();
+        names.sort_by(|a, b| natural_cmp(a, b));
+
+        let mut drives = Vec::new();
+        for name in names {
+            if name.starts_with("loop") || name.starts_with("ram") || name.starts_with("zram") {
+                continue;
+            }
+            // eMMC boot and RPMB areas show up as block devices of their
+            // own, like `mmcblk0boot0` and `mmcblk0rpmb`, but they're part
+            // of `mmcblk0`.
+            if name.starts_with("mmcblk") && (name.contains("boot") || name.ends_with("rpmb")) {
+                continue;
+            }
+            let dir = root.join(&name);
+            // Resolves to something like
+            // `/sys/devices/pci0000:00/0000:00:17.0/ata1/host0/.../block/sda`.
+            let real = fs::canonicalize(&dir)
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_default();
+            // Device mapper, md and friends aren't physical drives.
+            if real.contains("/devices/virtual/") {
+                continue;
+            }
+
+            let device = dir.join("device");
+            let flag = |path: &str| read_trimmed(dir.join(path)).as_deref() == Some("1");
+
+            drives.push(Drive {
+                model: read_trimmed(device.join("model"))
+                    .or_else(|| read_trimmed(device.join("name")))
+                    .unwrap_or_default(),
+                vendor: read_trimmed(device.join("vendor")).unwrap_or_default(),
+                size: Self::sectors(&dir),
+                rotational: flag("queue/rotational"),
+                removable: flag("removable"),
+                transport: Self::transport(&name, &real),
+                partitions: Self::partitions(&dir, &name),
+                name,
+            });
+        }
+
+        Drives(drives)
+    }
+
+    /// Reads `size`, which is always in 512 byte sectors regardless of the
+    /// drive's logical block size, and converts it to bytes.
+    fn sectors(dir: &Path) -> u64 {
+        read_trimmed(dir.join("size"))
+            .and_then(|v| v.parse::<u64>().ok())
+            .unwrap_or(0)
+            * 512
+    }
+
+    fn transport(name: &str, real: &str) -> &'static str {
+        if name.starts_with("nvme") {
+            "nvme"
+        } else if real.contains("/usb") {
+            "usb"
+        } else if name.starts_with("vd") || real.contains("/virtio") {
+            "virtio"
+        } else if name.starts_with("xvd") {
+            "xen"
+        } else if name.starts_with("mmcblk") {
+            "mmc"
+        } else if real.contains("/ata") {
+            "sata"
+        } else if real.contains("/host") {
+            "scsi"
+        } else {
+            "unknown"
+        }
+    }
+
+    fn partitions(dir: &Path, name: &str) -> Vec<Partition> {
+        let mut partitions = fs::read_dir(dir)
+            .map(|d| {
+                d.flatten()
+                    .filter(|e| {
+                        e.file_name().to_string_lossy().starts_with(name)
+                            && e.path().join("partition").is_file()
+                    })
+                    .map(|e| Partition {
+                        name: e.file_name().to_string_lossy().to_string(),
+                        size: Self::sectors(&e.path()),
+                    })
+                    .collect::<Vec<Partition>>()
+            })
+            .unwrap_or_default();
+        partitions.sort_by(|a, b| natural_cmp(&a.name, &b.name));
+        partitions
+    }
+
+    fn table<'lua>(&self, lua: &'lua Lua) -> LuaResult<LuaTable<'lua>> {
+        let t = lua.create_table()?;
+        for (i, drive) in self.0.iter().enumerate() {
+            let d = lua.create_table()?;
+            d.set("name", drive.name.as_str())?;
+            d.set("model", drive.model.as_str())?;
+            d.set("vendor", drive.vendor.as_str())?;
+            d.set("size", drive.size)?;
+            d.set("rotational", drive.rotational)?;
+            d.set("removable", drive.removable)?;
+            d.set("transport", drive.transport)?;
+            let partitions = lua.create_table()?;
+            for (j, partition) in drive.partitions.iter().enumerate() {
+                let p = lua.create_table()?;
+                p.set("name", partition.name.as_str())?;
+                p.set("size", partition.size)?;
+                partitions.raw_set((j + 1) as i64, p)?;
+            }
+            d.set("partitions", partitions)?;
+            t.raw_set((i + 1) as i64, d)?;
+        }
+        Ok(t)
+    }
+}
+
+/// Compares device names with the numbers in them compared as numbers, so
+/// `nvme0n1p2` comes before `nvme0n1p10`.
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (a, b);
+    loop {
+        let (Some(x), Some(y)) = (a.chars().next(), b.chars().next()) else {
+            return a.len().cmp(&b.len());
+        };
+        let ordering = if x.is_ascii_digit() && y.is_ascii_digit() {
+            let digits = |s: &str| s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+            let (m, rest) = a.split_at(digits(a));
+            a = rest;
+            let (n, rest) = b.split_at(digits(b));
+            b = rest;
+            let (m, n) = (m.trim_start_matches('0'), n.trim_start_matches('0'));
+            m.len().cmp(&n.len()).then_with(|| m.cmp(n))
+        } else {
+            a = &a[x.len_utf8()..];
+            b = &b[y.len_utf8()..];
+            x.cmp(&y)
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
+impl Inject for Drives {
+    fn inject(&self, lua: &mut Lua) {
+        match self.table(lua).and_then(|t| lua.globals().set("drives", t)) {
+            Ok(_) => (),
+            Err(e) => {
+                errors::handle(&format!("{}{}", errors::LUA, e));
+                panic!();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::os::unix::fs::symlink;
+    use std::path::PathBuf;
+
+    /// A made up `/sys` in a temp dir, removed when it's dropped. Like the
+    /// real one, `block/` holds links into `devices/`.
+    struct FakeSysfs(PathBuf);
+
+    impl FakeSysfs {
+        fn new(name: &str) -> Self {
+            let root = std::env::temp_dir().join(format!(
+                "freshfetch-drives-{}-{}",
+                name,
+                std::process::id()
+            ));
+            let _ = fs::remove_dir_all(&root);
+            fs::create_dir_all(root.join("block")).unwrap();
+            FakeSysfs(root)
+        }
+
+        /// Adds the block device `name` at `devices/<device>/block/<name>`,
+        /// with `files` in it.
+        fn drive(&self, device: &str, name: &str, files: &[(&str, &str)]) -> &Self {
+            let dir = self.0.join("devices").join(device).join("block").join(name);
+            for (path, contents) in files {
+                let path = dir.join(path);
+                fs::create_dir_all(path.parent().unwrap()).unwrap();
+                fs::write(path, contents).unwrap();
+            }
+            fs::create_dir_all(&dir).unwrap();
+            symlink(&dir, self.0.join("block").join(name)).unwrap();
+            self
+        }
+    }
+
+    impl Drop for FakeSysfs {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn lists_physical_drives() {
+        let sysfs = FakeSysfs::new("list");
+        sysfs
+            .drive(
+                "pci0000:00/0000:00:17.0/ata1/host0/target0:0:0/0:0:0:0",
+                "sda",
+                &[
+                    ("size", "1953525168\n"),
+                    ("queue/rotational", "1\n"),
+                    ("removable", "0\n"),
+                    ("device/model", "WDC WD10EZEX-08W\n"),
+                    ("device/vendor", "ATA\n"),
+                    ("sda1/partition", "1\n"),
+                    ("sda1/size", "1048576\n"),
+                    ("sda10/partition", "10\n"),
+                    ("sda10/size", "2048\n"),
+                    ("sda2/partition", "2\n"),
+                    ("sda2/size", "4096\n"),
+                ],
+            )
+            .drive(
+                "pci0000:00/0000:00:14.0/usb2/2-1/2-1:1.0/host6/target6:0:0/6:0:0:0",
+                "sdb",
+                &[("size", "60063744\n"), ("removable", "1\n")],
+            )
+            .drive(
+                "pci0000:00/0000:00:1d.0/0000:3d:00.0/nvme/nvme0",
+                "nvme0n1",
+                &[
+                    ("size", "1000215216\n"),
+                    ("device/model", "Samsung SSD 980\n"),
+                ],
+            )
+            .drive(
+                "platform/fe320000.mmc/mmc_host/mmc0/mmc0:0001",
+                "mmcblk0",
+                &[("size", "30535680\n"), ("device/name", "SC32G\n")],
+            )
+            .drive(
+                "platform/fe320000.mmc/mmc_host/mmc0/mmc0:0001",
+                "mmcblk0boot0",
+                &[],
+            )
+            .drive(
+                "platform/fe320000.mmc/mmc_host/mmc0/mmc0:0001",
+                "mmcblk0boot1",
+                &[],
+            )
+            .drive(
+                "platform/fe320000.mmc/mmc_host/mmc0/mmc0:0001",
+                "mmcblk0rpmb",
+                &[],
+            )
+            .drive("virtual", "loop0", &[("size", "8\n")])
+            .drive("virtual", "zram0", &[("size", "8\n")])
+            .drive("virtual", "dm-0", &[("size", "8\n")]);
+
+        let drives = Drives::from_sysfs(&sysfs.0.join("block")).0;
+        let names = drives
+            .iter()
+            .map(|d| d.name.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(names, ["mmcblk0", "nvme0n1", "sda", "sdb"]);
+
+        let transports = drives.iter().map(|d| d.transport).collect::<Vec<&str>>();
+        assert_eq!(transports, ["mmc", "nvme", "sata", "usb"]);
+
+        assert_eq!(drives[0].model, "SC32G");
+        assert_eq!(drives[1].model, "Samsung SSD 980");
+        let sda = &drives[2];
+        assert_eq!(sda.model, "WDC WD10EZEX-08W");
+        assert_eq!(sda.vendor, "ATA");
+        assert_eq!(sda.size, 1953525168 * 512);
+        assert!(sda.rotational);
+        assert!(!sda.removable);
+        let partitions = sda
+            .partitions
+            .iter()
+            .map(|p| (p.name.as_str(), p.size))
+            .collect::<Vec<(&str, u64)>>();
+        assert_eq!(
+            partitions,
+            [
+                ("sda1", 1048576 * 512),
+                ("sda2", 4096 * 512),
+                ("sda10", 2048 * 512)
+            ]
+        );
+        assert!(drives[3].removable);
+        assert!(drives[3].partitions.is_empty());
+    }
+
+    #[test]
+    fn sorts_names_naturally() {
+        let mut names = vec![
+            "nvme0n1p10",
+            "sda10",
+            "nvme0n1p2",
+            "sdb",
+            "nvme0n1p1",
+            "sda2",
+            "nvme10n1",
+            "nvme2n1",
+            "sda",
+        ];
+        names.sort_by(|a, b| natural_cmp(a, b));
+        assert_eq!(
+            names,
+            [
+                "nvme0n1p1",
+                "nvme0n1p2",
+                "nvme0n1p10",
+                "nvme2n1",
+                "nvme10n1",
+                "sda",
+                "sda2",
+                "sda10",
+                "sdb",
+            ]
+        );
+    }
+}
@@ -1,13 +1,20 @@
 use super::kernel;
+use super::utils::read_trimmed;
 use crate::{errors, mlua, regex, Inject};
 use kernel::Kernel;
 use mlua::prelude::*;
 use regex::Regex;
+use std::env;
 use std::fs;
+use std::path::Path;
 
 #[derive(Clone, Debug)]
 pub(crate) struct Host {
     pub model: String,
+    pub version: String,
+    pub vendor: String,
+    pub virtualization: Option<String>,
+    pub container: Option<String>,
 }
 
 impl Host {
@@ -16,13 +23,158 @@ impl Host {
             return None;
         }
 
-        let raw = fs::read_to_string("/sys/devices/virtual/dmi/id/product_name").ok()?;
-        let cleaned = Self::clean_product_name(&raw);
+        let dmi = Path::new("/sys/devices/virtual/dmi/id");
+        let dmi_field = |name: &str| {
+            read_trimmed(dmi.join(name))
+                .map(|v| Self::clean_product_name(&v))
+                .unwrap_or_default()
+        };
 
-        if cleaned.is_empty() {
+        let mut model = dmi_field("product_name");
+        let mut version = dmi_field("product_version");
+        let vendor = dmi_field("sys_vendor");
+
+        // SBCs and most ARM boards have no DMI tables, but the device tree
+        // carries a human readable model string instead.
+        if model.is_empty() {
+            model = [
+                "/sys/firmware/devicetree/base/model",
+                "/proc/device-tree/model",
+            ]
+            .iter()
+            .find_map(read_trimmed)
+            .map(|v| v.trim_end_matches('\0').to_string())
+            .unwrap_or_default();
+            version = String::new();
+        }
+
+        // Placeholder versions are noise, only keep ones that actually tell
+        // the user something.
+        if version == model || version.eq_ignore_ascii_case("System Version") {
+            version = String::new();
+        }
+
+        let root = Path::new("/");
+        let virtualization = Self::detect_virtualization(root);
+        let container = Self::detect_container(root, env::var("container").ok());
+
+        if model.is_empty() && virtualization.is_none() && container.is_none() {
             None
         } else {
-            Some(Host { model: cleaned })
+            Some(Host {
+                model,
+                version,
+                vendor,
+                virtualization,
+                container,
+            })
+        }
+    }
+
+    /// Works out the hypervisor from the files below `root`, which is
+    /// normally `/`.
+    fn detect_virtualization(root: &Path) -> Option<String> {
+        // WSL runs a real Linux kernel under Hyper-V, but users care about it
+        // being WSL, so check it first.
+        if let Some(release) = read_trimmed(root.join("proc/sys/kernel/osrelease")) {
+            let release = release.to_lowercase();
+            if release.contains("wsl2") {
+                return Some(String::from("WSL2"));
+            } else if release.contains("microsoft") {
+                return Some(String::from("WSL"));
+            }
+        }
+
+        let dmi = root.join("sys/devices/virtual/dmi/id");
+        let fields = ["sys_vendor", "product_name", "bios_vendor", "board_vendor"]
+            .iter()
+            .filter_map(|f| read_trimmed(dmi.join(f)))
+            .collect::<Vec<String>>();
+
+        static VENDORS: &[(&str, &str)] = &[
+            ("KVM", "KVM"),
+            ("QEMU", "QEMU"),
+            ("VMware", "VMware"),
+            ("VMW", "VMware"),
+            ("innotek GmbH", "VirtualBox"),
+            ("VirtualBox", "VirtualBox"),
+            ("Xen", "Xen"),
+            ("Bochs", "Bochs"),
+            ("Parallels", "Parallels"),
+            ("BHYVE", "bhyve"),
+            ("Amazon EC2", "Amazon EC2"),
+            ("Google Compute Engine", "Google Compute Engine"),
+        ];
+        for (needle, name) in VENDORS {
+            if fields.iter().any(|f| f.contains(needle)) {
+                return Some(String::from(*name));
+            }
+        }
+        if fields.iter().any(|f| f == "Microsoft Corporation")
+            && fields.iter().any(|f| f == "Virtual Machine")
+        {
+            return Some(String::from("Hyper-V"));
+        }
+
+        if read_trimmed(root.join("sys/hypervisor/type")).as_deref() == Some("xen") {
+            return Some(String::from("Xen"));
+        }
+        if read_trimmed(root.join("proc/device-tree/hypervisor/compatible"))
+            .map(|v| v.contains("linux,kvm"))
+            .unwrap_or(false)
+        {
+            return Some(String::from("KVM"));
+        }
+
+        None
+    }
+
+    /// Works out the container runtime from the files below `root`, which
+    /// is normally `/`, and the `container` environment variable.
+    fn detect_container(root: &Path, env_marker: Option<String>) -> Option<String> {
+        if root.join(".dockerenv").exists() {
+            return Some(String::from("Docker"));
+        }
+        if root.join("run/.containerenv").exists() {
+            return Some(String::from("Podman"));
+        }
+
+        // systemd, nspawn, LXC and most OCI runtimes set this.
+        let marker = read_trimmed(root.join("run/systemd/container"))
+            .or(env_marker)
+            .unwrap_or_default();
+        if !marker.is_empty() {
+            return Some(Self::container_name(&marker));
+        }
+
+        if root.join("dev/lxd/sock").exists() {
+            return Some(String::from("LXD"));
+        }
+        if let Ok(cgroup) = fs::read_to_string(root.join("proc/1/cgroup")) {
+            for (needle, name) in [
+                ("docker", "Docker"),
+                ("libpod", "Podman"),
+                ("lxc", "LXC"),
+                ("kubepods", "Kubernetes"),
+            ] {
+                if cgroup.contains(needle) {
+                    return Some(String::from(name));
+                }
+            }
+        }
+
+        None
+    }
+
+    fn container_name(marker: &str) -> String {
+        match marker {
+            "docker" => String::from("Docker"),
+            "podman" => String::from("Podman"),
+            "lxc" | "lxc-libvirt" => String::from("LXC"),
+            "systemd-nspawn" => String::from("systemd-nspawn"),
+            "oci" => String::from("OCI"),
+            "wsl" => String::from("WSL"),
+            other => other.to_string(),
         }
     }
 
@@ -59,6 +211,18 @@ impl Inject for Host {
             if let Err(e) = t.set("model", self.model.as_str()) {
                 errors::handle(&format!("{}{}", errors::LUA, e));
             }
+            if let Err(e) = t.set("version", self.version.as_str()) {
+                errors::handle(&format!("{}{}", errors::LUA, e));
+            }
+            if let Err(e) = t.set("vendor", self.vendor.as_str()) {
+                errors::handle(&format!("{}{}", errors::LUA, e));
+            }
+            if let Err(e) = t.set("virtualization", self.virtualization.as_deref()) {
+                errors::handle(&format!("{}{}", errors::LUA, e));
+            }
+            if let Err(e) = t.set("container", self.container.as_deref()) {
+                errors::handle(&format!("{}{}", errors::LUA, e));
+            }
             if let Err(e) = globals.set("host", t) {
                 errors::handle(&format!("{}{}", errors::LUA, e));
             }
@@ -67,3 +231,151 @@ impl Inject for Host {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::path::PathBuf;
+
+    /// A made up `/` in a temp dir, removed when it's dropped.
+    struct FakeRoot(PathBuf);
+
+    impl FakeRoot {
+        fn new(name: &str) -> Self {
+            let root =
+                env::temp_dir().join(format!("freshfetch-host-{}-{}", name, std::process::id()));
+            let _ = fs::remove_dir_all(&root);
+            fs::create_dir_all(&root).unwrap();
+            FakeRoot(root)
+        }
+
+        fn file(&self, path: &str, contents: &str) -> &Self {
+            let path = self.0.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+            self
+        }
+    }
+
+    impl Drop for FakeRoot {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn detects_docker() {
+        let root = FakeRoot::new("docker");
+        root.file(".dockerenv", "");
+        assert_eq!(
+            Host::detect_container(&root.0, None).as_deref(),
+            Some("Docker")
+        );
+
+        // Older Docker only shows up in the cgroups.
+        let root = FakeRoot::new("docker-cgroup");
+        root.file("proc/1/cgroup", "0::/system.slice/docker-0123abcd.scope\n");
+        assert_eq!(
+            Host::detect_container(&root.0, None).as_deref(),
+            Some("Docker")
+        );
+    }
+
+    #[test]
+    fn detects_podman() {
+        let root = FakeRoot::new("podman");
+        root.file("run/.containerenv", "engine=\"podman-4.9.3\"\n");
+        assert_eq!(
+            Host::detect_container(&root.0, None).as_deref(),
+            Some("Podman")
+        );
+    }
+
+    #[test]
+    fn detects_lxc() {
+        let root = FakeRoot::new("lxc");
+        root.file("run/systemd/container", "lxc\n");
+        assert_eq!(
+            Host::detect_container(&root.0, None).as_deref(),
+            Some("LXC")
+        );
+
+        let root = FakeRoot::new("lxc-env");
+        assert_eq!(
+            Host::detect_container(&root.0, Some(String::from("lxc-libvirt"))).as_deref(),
+            Some("LXC")
+        );
+    }
+
+    #[test]
+    fn detects_no_container() {
+        let root = FakeRoot::new("bare");
+        root.file("proc/1/cgroup", "0::/init.scope\n");
+        assert_eq!(Host::detect_container(&root.0, None), None);
+    }
+
+    #[test]
+    fn detects_kvm() {
+        let root = FakeRoot::new("kvm");
+        root.file("proc/sys/kernel/osrelease", "6.8.0-45-generic\n")
+            .file("sys/devices/virtual/dmi/id/sys_vendor", "QEMU\n")
+            .file(
+                "sys/devices/virtual/dmi/id/product_name",
+                "Standard PC (Q35 + ICH9, 2009)\n",
+            )
+            .file("sys/devices/virtual/dmi/id/bios_vendor", "SeaBIOS\n");
+        assert_eq!(
+            Host::detect_virtualization(&root.0).as_deref(),
+            Some("QEMU")
+        );
+
+        let root = FakeRoot::new("kvm-dmi");
+        root.file("sys/devices/virtual/dmi/id/product_name", "KVM\n");
+        assert_eq!(Host::detect_virtualization(&root.0).as_deref(), Some("KVM"));
+
+        // ARM guests have no DMI, but the device tree says it's KVM.
+        let root = FakeRoot::new("kvm-dt");
+        root.file("proc/device-tree/hypervisor/compatible", "linux,kvm\0");
+        assert_eq!(Host::detect_virtualization(&root.0).as_deref(), Some("KVM"));
+    }
+
+    #[test]
+    fn detects_wsl_and_hyper_v() {
+        let root = FakeRoot::new("wsl");
+        root.file(
+            "proc/sys/kernel/osrelease",
+            "5.15.153.1-microsoft-standard-WSL2\n",
+        )
+        .file(
+            "sys/devices/virtual/dmi/id/sys_vendor",
+            "Microsoft Corporation\n",
+        );
+        assert_eq!(
+            Host::detect_virtualization(&root.0).as_deref(),
+            Some("WSL2")
+        );
+
+        let root = FakeRoot::new("hyper-v");
+        root.file(
+            "sys/devices/virtual/dmi/id/sys_vendor",
+            "Microsoft Corporation\n",
+        )
+        .file(
+            "sys/devices/virtual/dmi/id/product_name",
+            "Virtual Machine\n",
+        );
+        assert_eq!(
+            Host::detect_virtualization(&root.0).as_deref(),
+            Some("Hyper-V")
+        );
+    }
+
+    #[test]
+    fn detects_bare_metal() {
+        let root = FakeRoot::new("metal");
+        root.file("sys/devices/virtual/dmi/id/sys_vendor", "LENOVO\n")
+            .file("sys/devices/virtual/dmi/id/product_name", "21CB\n");
+        assert_eq!(Host::detect_virtualization(&root.0), None);
+    }
+}
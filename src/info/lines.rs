@@ -0,0 +1,273 @@
+use crate::mlua;
+
+use crate::color;
+use crate::utils::text;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use mlua::prelude::*;
+
+/// A `line()` call. These are held on to until the whole info has been
+/// printed, so the values can be lined up into a column.
+struct Line {
+    /// The icon and label, with their colors.
+    label: String,
+    separator: String,
+    /// The value, one entry per line.
+    values: Vec<String>,
+    align: bool,
+}
+
+/// Stands in for the `i`th `Line` in the printed info, in the same form as
+/// the markers in `layout::compose::Overflow`.
+fn marker(i: usize) -> String {
+    format!("\u{001b}_freshfetch:line:{}\u{001b}\\", i)
+}
+
+fn sgr(code: &str) -> String {
+    if color::enabled() {
+        format!("\u{001b}[{}m", code)
+    } else {
+        String::new()
+    }
+}
+
+/// Gets the `i`th entry of the `distroColors` global, or nothing if it
+/// isn't set.
+fn distro_color(lua: &Lua, i: i64) -> String {
+    lua.globals()
+        .get::<_, LuaTable>("distroColors")
+        .and_then(|t| t.get::<_, String>(i))
+        .unwrap_or_default()
+}
+
+/// Turns a value passed to `line()` into its lines: a string or number is
+/// one line, a table of them is one line each. Empty ones are dropped.
+fn values(lua: &Lua, value: LuaValue) -> LuaResult<Vec<String>> {
+    let values = match value {
+        LuaValue::Nil => Vec::new(),
+        LuaValue::Table(t) => t.sequence_values::<LuaValue>().collect::<LuaResult<_>>()?,
+        v => vec![v],
+    };
+    let mut to_return = Vec::new();
+    for value in values {
+        if let Some(s) = lua.coerce_string(value)? {
+            let s = s.to_str()?;
+            if !s.is_empty() {
+                to_return.push(s.to_string());
+            }
+        }
+    }
+    Ok(to_return)
+}
+
+/// The Lua API for building the info out of labelled lines instead of
+/// concatenating escape codes by hand.
+#[derive(Clone, Default)]
+pub(crate) struct Lines {
+    lines: Rc<RefCell<Vec<Line>>>,
+}
+
+impl Lines {
+    /// Adds `line(label, value, opts)` and `section(title, opts)`.
+    ///
+    /// `line` prints `label: value`, or nothing at all if `value` is `nil`
+    /// or empty. `value` can also be a table, for values that take several
+    /// lines. `opts` can have an `icon` to go before the label, a `color`
+    /// for the label (`distroColors[2]` by default), a `separator` (`": "`
+    /// by default), and `align = true` to line the value up with the other
+    /// aligned lines.
+    ///
+    /// `section` prints `title` in bold. `opts` can have an `icon`, a
+    /// `color` (`distroColors[1]` by default), and an `underline`
+    /// character to repeat under the title.
+    pub fn register(&self, lua: &Lua) -> LuaResult<()> {
+        let lines = self.lines.clone();
+        let line = lua.create_function(
+            move |lua, (label, value, opts): (String, LuaValue, Option<LuaTable>)| {
+                let values = values(lua, value)?;
+                if values.is_empty() {
+                    return Ok(());
+                }
+                let opts = match opts {
+                    Some(opts) => opts,
+                    None => lua.create_table()?,
+                };
+                let icon = opts
+                    .get::<_, Option<String>>("icon")?
+                    .map(|icon| icon + " ")
+                    .unwrap_or_default();
+                let color = match opts.get::<_, Option<String>>("color")? {
+                    Some(color) => color,
+                    None => distro_color(lua, 2),
+                };
+
+                let i = {
+                    let mut lines = lines.borrow_mut();
+                    lines.push(Line {
+                        label: format!("{}{}{}{}{}", icon, sgr("1"), color, label, sgr("0")),
+                        separator: opts
+                            .get::<_, Option<String>>("separator")?
+                            .unwrap_or_else(|| String::from(": ")),
+                        values,
+                        align: opts.get::<_, Option<bool>>("align")?.unwrap_or(false),
+                    });
+                    lines.len() - 1
+                };
+                lua.globals()
+                    .get::<_, LuaFunction>("print")?
+                    .call::<_, ()>(marker(i))
+            },
+        )?;
+
+        let section = lua.create_function(|lua, (title, opts): (String, Option<LuaTable>)| {
+            let opts = match opts {
+                Some(opts) => opts,
+                None => lua.create_table()?,
+            };
+            let icon = opts
+                .get::<_, Option<String>>("icon")?
+                .map(|icon| icon + " ")
+                .unwrap_or_default();
+            let color = match opts.get::<_, Option<String>>("color")? {
+                Some(color) => color,
+                None => distro_color(lua, 1),
+            };
+
+            let print = lua.globals().get::<_, LuaFunction>("print")?;
+            let title = format!("{}{}{}{}", icon, sgr("1"), color, title);
+            print.call::<_, ()>(format!("{}{}", title, sgr("0")))?;
+            if let Some(underline) = opts.get::<_, Option<String>>("underline")? {
+                print.call::<_, ()>(underline.repeat(text::display_width(&title)))?;
+            }
+            Ok(())
+        })?;
+
+        let globals = lua.globals();
+        globals.set("line", line)?;
+        globals.set("section", section)?;
+        Ok(())
+    }
+
+    /// Swaps the stand-ins `line()` printed in `rendered` for the lines
+    /// themselves, now that it's known how wide the aligned labels are.
+    pub fn resolve(&self, rendered: &str) -> String {
+        let lines = self.lines.borrow();
+        let width =
+            |line: &Line| text::display_width(&line.label) + text::display_width(&line.separator);
+        let column = lines
+            .iter()
+            .filter(|line| line.align)
+            .map(width)
+            .max()
+            .unwrap_or(0);
+
+        let mut to_return = rendered.to_string();
+        for (i, line) in lines.iter().enumerate() {
+            let padding = if line.align { column - width(line) } else { 0 };
+            let first = format!(
+                "{}{}{}{}",
+                line.label,
+                line.separator,
+                " ".repeat(padding),
+                line.values[0]
+            );
+            // Values after the first go under it.
+            let indent = " ".repeat(width(line) + padding);
+            let rest = line.values[1..]
+                .iter()
+                .map(|value| format!("\n{}{}", indent, value))
+                .collect::<String>();
+            to_return = to_return.replace(&marker(i), &(first + &rest));
+        }
+        to_return
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Runs `script` with `line()` and `section()`, and returns what it
+    /// printed once the lines are resolved, without colors.
+    fn run(script: &str) -> String {
+        let lua = Lua::new();
+        let lines = Lines::default();
+        lines.register(&lua).unwrap();
+        lua.load(
+            r#"
+            printed = {}
+            print = function(s) printed[#printed + 1] = s end
+            "#,
+        )
+        .exec()
+        .unwrap();
+        lua.load(script).exec().unwrap();
+        let printed = lua
+            .globals()
+            .get::<_, Vec<String>>("printed")
+            .unwrap()
+            .join("\n");
+        text::strip_ansi(&lines.resolve(&printed))
+    }
+
+    #[test]
+    fn lines_up_aligned_values() {
+        let printed = run(r#"
+            line("OS", "Arch", {align = true})
+            line("Kernel", "6.1", {align = true})
+            line("Up", "1h")
+        "#);
+        assert_eq!(printed, "OS:     Arch\nKernel: 6.1\nUp: 1h");
+    }
+
+    #[test]
+    fn hides_empty_values() {
+        let printed = run(r#"
+            line("Nil", nil)
+            line("Empty", "")
+            line("None", {})
+            line("Some", {"", "x"})
+            line("Number", 42)
+        "#);
+        assert_eq!(printed, "Some: x\nNumber: 42");
+    }
+
+    #[test]
+    fn puts_later_values_under_the_first() {
+        let printed = run(r#"
+            line("GPU", {"one", "two"}, {align = true})
+            line("Resolution", "1080p", {align = true})
+        "#);
+        assert_eq!(
+            printed,
+            "GPU:        one\n            two\nResolution: 1080p"
+        );
+    }
+
+    #[test]
+    fn uses_separators_and_icons() {
+        let printed = run(r##"
+            section("Title", {icon = "#", underline = "-"})
+            line("CPU", "x", {icon = "*", separator = " -> "})
+        "##);
+        assert_eq!(printed, "# Title\n-------\n* CPU -> x");
+    }
+
+    #[test]
+    fn resolves_only_its_own_markers() {
+        let lines = Lines::default();
+        lines.lines.borrow_mut().push(Line {
+            label: String::from("A"),
+            separator: String::from(": "),
+            values: vec![String::from("b")],
+            align: false,
+        });
+        let rendered = format!("before\n{}\n{}after", marker(0), marker(1));
+        assert_eq!(
+            lines.resolve(&rendered),
+            format!("before\nA: b\n{}after", marker(1))
+        );
+    }
+}
@@ -0,0 +1,217 @@
+use crate::mlua;
+use crate::sysinfo;
+
+use super::kernel;
+use super::utils::{self, read_trimmed};
+use crate::errors;
+
+use mlua::prelude::*;
+use sysinfo::{ProcessExt, ProcessStatus, SystemExt};
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::Inject;
+use kernel::Kernel;
+use utils::get_system;
+
+/// How many processes are listed in `load.topMemory` and `load.topCpu`.
+const TOP_PROCESSES: usize = 5;
+
+/// How long sysinfo needs between two refreshes for CPU usage to mean
+/// anything on Linux. Later versions of sysinfo have this as
+/// `MINIMUM_CPU_UPDATE_INTERVAL`.
+const CPU_UPDATE_INTERVAL: Duration = Duration::from_millis(200);
+
+#[derive(Clone, Debug)]
+pub(crate) struct ProcessInfo {
+    pub pid: String,
+    pub name: String,
+    /// Resident memory, in KiB like `memory.used`.
+    pub memory: u64,
+    pub cpu: f32,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct Load {
+    pub one: f64,
+    pub five: f64,
+    pub fifteen: f64,
+    pub processes: usize,
+    pub running: usize,
+    pub threads: usize,
+    pub open_files: u64,
+    pub max_files: u64,
+    pub top_memory: Vec<ProcessInfo>,
+    /// When `Info::new` last refreshed the system, which the CPU usage in
+    /// `topCpu` is measured from.
+    pub refreshed: Instant,
+}
+
+impl Load {
+    pub fn new(k: &Kernel, refreshed: Instant) -> Option<Self> {
+        if k.name != "Linux" {
+            return None;
+        }
+
+        let system = get_system();
+
+        // `/proc/loadavg` looks like "0.42 0.37 0.30 2/1337 4242", where the
+        // fourth field counts runnable/total scheduling entities (threads).
+        let loadavg = read_trimmed("/proc/loadavg").unwrap_or_default();
+        let fields = loadavg.split_whitespace().collect::<Vec<&str>>();
+        let (one, five, fifteen) = if fields.len() >= 3 {
+            (
+                fields[0].parse().unwrap_or(0.0),
+                fields[1].parse().unwrap_or(0.0),
+                fields[2].parse().unwrap_or(0.0),
+            )
+        } else {
+            let avg = system.load_average();
+            (avg.one, avg.five, avg.fifteen)
+        };
+        let threads = fields
+            .get(3)
+            .and_then(|v| v.split_once('/'))
+            .and_then(|(_, total)| total.parse().ok())
+            .unwrap_or(0);
+
+        // `/proc/sys/fs/file-nr` is "allocated unused max".
+        let file_nr = read_trimmed("/proc/sys/fs/file-nr").unwrap_or_default();
+        let file_nr = file_nr
+            .split_whitespace()
+            .map(|v| v.parse::<u64>().unwrap_or(0))
+            .collect::<Vec<u64>>();
+        let (open_files, max_files) = if file_nr.len() >= 3 {
+            (file_nr[0].saturating_sub(file_nr[1]), file_nr[2])
+        } else {
+            (0, 0)
+        };
+
+        let mut processes = system
+            .processes()
+            .iter()
+            .map(|(pid, p)| {
+                (
+                    p.status(),
+                    ProcessInfo {
+                        pid: pid.to_string(),
+                        name: String::from(p.name()),
+                        memory: p.memory(),
+                        cpu: p.cpu_usage(),
+                    },
+                )
+            })
+            .collect::<Vec<(ProcessStatus, ProcessInfo)>>();
+
+        let running = processes
+            .iter()
+            .filter(|(status, _)| matches!(status, ProcessStatus::Run))
+            .count();
+
+        processes.sort_by(|a, b| b.1.memory.cmp(&a.1.memory));
+        let top_memory = processes
+            .iter()
+            .take(TOP_PROCESSES)
+            .map(|(_, p)| p.clone())
+            .collect();
+
+        Some(Load {
+            one,
+            five,
+            fifteen,
+            processes: processes.len(),
+            running,
+            threads,
+            open_files,
+            max_files,
+            top_memory,
+            refreshed,
+        })
+    }
+
+    /// The processes using the most CPU. CPU usage is computed between two
+    /// refreshes, and `Info::new` already did the first. Most of the
+    /// interval has usually gone by while the rest of the info was read, so
+    /// this only waits for what's left, without holding the system.
+    fn top_cpu(refreshed: Instant) -> Vec<ProcessInfo> {
+        if let Some(wait) = CPU_UPDATE_INTERVAL.checked_sub(refreshed.elapsed()) {
+            thread::sleep(wait);
+        }
+        let mut system = get_system();
+        system.refresh_processes();
+
+        let mut processes = system
+            .processes()
+            .iter()
+            .map(|(pid, p)| ProcessInfo {
+                pid: pid.to_string(),
+                name: String::from(p.name()),
+                memory: p.memory(),
+                cpu: p.cpu_usage(),
+            })
+            .collect::<Vec<ProcessInfo>>();
+        processes.sort_by(|a, b| {
+            b.cpu
+                .partial_cmp(&a.cpu)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        processes.truncate(TOP_PROCESSES);
+        processes
+    }
+
+    fn table<'lua>(&self, lua: &'lua Lua) -> LuaResult<LuaTable<'lua>> {
+        let t = lua.create_table()?;
+        t.set("one", self.one)?;
+        t.set("five", self.five)?;
+        t.set("fifteen", self.fifteen)?;
+        t.set("processes", self.processes)?;
+        t.set("running", self.running)?;
+        t.set("threads", self.threads)?;
+        t.set("openFiles", self.open_files)?;
+        t.set("maxFiles", self.max_files)?;
+        t.set("topMemory", processes_table(lua, &self.top_memory)?)?;
+
+        // Finding `topCpu` means waiting between two refreshes, so it's only
+        // done for layouts that read it.
+        let refreshed = self.refreshed;
+        let top_cpu =
+            lua.create_function(move |lua, (t, key): (LuaTable, LuaValue)| match key {
+                LuaValue::String(key) if key.as_bytes() == b"topCpu" => {
+                    let top_cpu = processes_table(lua, &Self::top_cpu(refreshed))?;
+                    t.raw_set("topCpu", top_cpu.clone())?;
+                    Ok(LuaValue::Table(top_cpu))
+                }
+                _ => Ok(LuaValue::Nil),
+            })?;
+        let meta = lua.create_table()?;
+        meta.set("__index", top_cpu)?;
+        t.set_metatable(Some(meta));
+        Ok(t)
+    }
+}
+
+fn processes_table<'lua>(lua: &'lua Lua, processes: &[ProcessInfo]) -> LuaResult<LuaTable<'lua>> {
+    let t = lua.create_table()?;
+    for (i, p) in processes.iter().enumerate() {
+        let pt = lua.create_table()?;
+        pt.set("pid", p.pid.as_str())?;
+        pt.set("name", p.name.as_str())?;
+        pt.set("memory", p.memory)?;
+        pt.set("cpu", p.cpu)?;
+        t.raw_set((i + 1) as i64, pt)?;
+    }
+    Ok(t)
+}
+
+impl Inject for Load {
+    fn inject(&self, lua: &mut Lua) {
+        match self.table(lua).and_then(|t| lua.globals().set("load", t)) {
+            Ok(_) => (),
+            Err(e) => {
+                errors::handle(&format!("{}{}", errors::LUA, e));
+                panic!();
+            }
+        }
+    }
+}
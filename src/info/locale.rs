@@ -0,0 +1,166 @@
+use crate::chrono;
+use crate::mlua;
+use crate::regex;
+
+use super::kernel;
+use super::utils::{parse_env_file, read_trimmed};
+use crate::errors;
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use chrono::Local;
+use mlua::prelude::*;
+use regex::Regex;
+
+use crate::Inject;
+use kernel::Kernel;
+
+#[derive(Clone, Debug)]
+pub(crate) struct Locale {
+    pub locale: String,
+    pub timezone: String,
+    pub time: String,
+    pub utc_offset: String,
+    pub keymap: String,
+    pub xkb_layout: String,
+    pub xkb_variant: String,
+}
+
+impl Locale {
+    pub fn new(k: &Kernel) -> Option<Self> {
+        if !matches!(k.name.as_str(), "Linux" | "BSD") {
+            return None;
+        }
+
+        let now = Local::now();
+        let (keymap, xkb_layout, xkb_variant) = Self::keyboard();
+
+        Some(Locale {
+            locale: Self::effective_locale(),
+            timezone: Self::timezone(),
+            time: now.format("%Y-%m-%d %H:%M").to_string(),
+            utc_offset: now.format("%:z").to_string(),
+            keymap,
+            xkb_layout,
+            xkb_variant,
+        })
+    }
+
+    /// Resolves the locale the same way libc does for messages: `LC_ALL`
+    /// wins, then the category variables, then `LANG`. Falls back to the
+    /// system-wide `/etc/locale.conf` when the environment is empty.
+    fn effective_locale() -> String {
+        [
+            "LC_ALL",
+            "LC_MESSAGES",
+            "LC_CTYPE",
+            "LC_TIME",
+            "LC_NUMERIC",
+            "LC_COLLATE",
+            "LC_MONETARY",
+            "LANG",
+        ]
+        .iter()
+        .find_map(|var| env::var(var).ok().filter(|v| !v.is_empty()))
+        .or_else(|| {
+            fs::read_to_string("/etc/locale.conf")
+                .ok()
+                .and_then(|conf| parse_env_file(&conf).remove("LANG"))
+        })
+        .unwrap_or_else(|| String::from("C"))
+    }
+
+    fn timezone() -> String {
+        if let Ok(tz) = env::var("TZ") {
+            if !tz.is_empty() {
+                return tz.trim_start_matches(':').to_string();
+            }
+        }
+        if let Ok(target) = fs::read_link("/etc/localtime") {
+            let target = target.to_string_lossy();
+            if let Some(i) = target.find("zoneinfo/") {
+                return target[i + "zoneinfo/".len()..].to_string();
+            }
+        }
+        read_trimmed("/etc/timezone").unwrap_or_default()
+    }
+
+    /// Returns the console keymap and the X11 layout and variant.
+    fn keyboard() -> (String, String, String) {
+        let mut keymap = String::new();
+        let mut layout = String::new();
+        let mut variant = String::new();
+
+        // systemd-based distros.
+        if let Ok(vconsole) = fs::read_to_string("/etc/vconsole.conf") {
+            let vconsole = parse_env_file(&vconsole);
+            keymap = vconsole.get("KEYMAP").cloned().unwrap_or_default();
+        }
+
+        // Debian and derivatives.
+        if let Ok(keyboard) = fs::read_to_string("/etc/default/keyboard") {
+            let keyboard = parse_env_file(&keyboard);
+            layout = keyboard.get("XKBLAYOUT").cloned().unwrap_or_default();
+            variant = keyboard.get("XKBVARIANT").cloned().unwrap_or_default();
+        }
+
+        // Xorg snippets, usually written by `localectl set-x11-keymap`.
+        if layout.is_empty() {
+            let dir = Path::new("/etc/X11/xorg.conf.d");
+            if let Ok(entries) = fs::read_dir(dir) {
+                let regex =
+                    Regex::new(r#"(?i)Option\s+"Xkb(Layout|Variant)"\s+"([^"]*)""#).unwrap();
+                let mut paths = entries
+                    .flatten()
+                    .map(|e| e.path())
+                    .filter(|p| p.extension().map(|e| e == "conf").unwrap_or(false))
+                    .collect::<Vec<_>>();
+                paths.sort();
+                for path in paths {
+                    let Ok(conf) = fs::read_to_string(&path) else {
+                        continue;
+                    };
+                    for caps in regex.captures_iter(&conf) {
+                        let value = caps[2].to_string();
+                        if caps[1].eq_ignore_ascii_case("layout") && layout.is_empty() {
+                            layout = value;
+                        } else if caps[1].eq_ignore_ascii_case("variant") && variant.is_empty() {
+                            variant = value;
+                        }
+                    }
+                }
+            }
+        }
+
+        (keymap, layout, variant)
+    }
+}
+
+impl Inject for Locale {
+    fn inject(&self, lua: &mut Lua) {
+        let globals = lua.globals();
+        match lua.create_table() {
+            Ok(t) => {
+                for (key, value) in [
+                    ("locale", &self.locale),
+                    ("timezone", &self.timezone),
+                    ("time", &self.time),
+                    ("utcOffset", &self.utc_offset),
+                    ("keymap", &self.keymap),
+                    ("xkbLayout", &self.xkb_layout),
+                    ("xkbVariant", &self.xkb_variant),
+                ] {
+                    if let Err(e) = t.set(key, value.as_str()) {
+                        errors::handle(&format!("{}{}", errors::LUA, e));
+                    }
+                }
+                if let Err(e) = globals.set("locale", t) {
+                    errors::handle(&format!("{}{}", errors::LUA, e));
+                }
+            }
+            Err(e) => errors::handle(&format!("{}{}", errors::LUA, e)),
+        }
+    }
+}
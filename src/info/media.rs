@@ -0,0 +1,206 @@
+use crate::dbus;
+use crate::mlua;
+
+use crate::errors;
+
+use std::time::Duration;
+
+use dbus::arg::{PropMap, RefArg};
+use dbus::blocking::stdintf::org_freedesktop_dbus::Properties;
+use dbus::blocking::Connection;
+use mlua::prelude::*;
+
+use crate::Inject;
+
+const MPRIS_PREFIX: &str = "org.mpris.MediaPlayer2.";
+const MPRIS_PATH: &str = "/org/mpris/MediaPlayer2";
+const MPRIS_PLAYER: &str = "org.mpris.MediaPlayer2.Player";
+
+/// Players that don't answer within this are skipped, so a hung player
+/// can't stall the whole fetch.
+const TIMEOUT: Duration = Duration::from_millis(150);
+
+#[derive(Clone, Debug)]
+pub(crate) struct Media {
+    pub player: String,
+    pub artist: String,
+    pub title: String,
+    pub album: String,
+    pub status: String,
+    /// How far into the track the player is, in seconds.
+    pub position: u64,
+    /// How long the track is, in seconds, or 0 if the player doesn't say.
+    pub length: u64,
+}
+
+impl Media {
+    /// Returns what's playing on the session bus, preferring players that
+    /// are actually playing over paused ones.
+    pub fn new() -> Option<Self> {
+        let conn = Connection::new_session().ok()?;
+        let bus = conn.with_proxy("org.freedesktop.DBus", "/org/freedesktop/DBus", TIMEOUT);
+        let (names,): (Vec<String>,) = bus
+            .method_call("org.freedesktop.DBus", "ListNames", ())
+            .ok()?;
+
+        let mut paused = None;
+        for name in names.iter().filter(|n| n.starts_with(MPRIS_PREFIX)) {
+            let Some(media) = Self::query(&conn, name) else {
+                continue;
+            };
+            match media.status.as_str() {
+                "Playing" => return Some(media),
+                "Paused" if paused.is_none() => paused = Some(media),
+                _ => (),
+            }
+        }
+        paused
+    }
+
+    fn query(conn: &Connection, name: &str) -> Option<Self> {
+        let proxy = conn.with_proxy(name, MPRIS_PATH, TIMEOUT);
+        let status: String = proxy.get(MPRIS_PLAYER, "PlaybackStatus").ok()?;
+        let metadata: PropMap = proxy.get(MPRIS_PLAYER, "Metadata").ok()?;
+        // Not every player has a position, like streams.
+        let position: Option<i64> = proxy.get(MPRIS_PLAYER, "Position").ok();
+        Some(Self::from_properties(name, status, &metadata, position))
+    }
+
+    /// Builds the media from a player's bus `name` and its `PlaybackStatus`,
+    /// `Metadata` and `Position` properties.
+    fn from_properties(
+        name: &str,
+        status: String,
+        metadata: &PropMap,
+        position: Option<i64>,
+    ) -> Self {
+        let string = |key: &str| {
+            metadata
+                .get(key)
+                .and_then(|v| v.0.as_str().map(String::from))
+                .unwrap_or_default()
+        };
+        // `xesam:artist` is a list of strings.
+        let artist = metadata
+            .get("xesam:artist")
+            .and_then(|v| v.0.as_iter())
+            .map(|artists| {
+                artists
+                    .filter_map(|a| a.as_str().map(String::from))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            })
+            .unwrap_or_default();
+        // `mpris:length` and `Position` are in microseconds, and some
+        // players send the length unsigned.
+        let length = metadata
+            .get("mpris:length")
+            .and_then(|v| v.0.as_i64().or_else(|| v.0.as_u64().map(|v| v as i64)))
+            .unwrap_or(0);
+
+        Media {
+            player: name.trim_start_matches(MPRIS_PREFIX).to_string(),
+            artist,
+            title: string("xesam:title"),
+            album: string("xesam:album"),
+            status,
+            position: (position.unwrap_or(0).max(0) / 1_000_000) as u64,
+            length: (length.max(0) / 1_000_000) as u64,
+        }
+    }
+}
+
+impl Inject for Media {
+    fn inject(&self, lua: &mut Lua) {
+        match lua.create_table() {
+            Ok(t) => {
+                for (key, value) in [
+                    ("player", &self.player),
+                    ("artist", &self.artist),
+                    ("title", &self.title),
+                    ("album", &self.album),
+                    ("status", &self.status),
+                ] {
+                    if let Err(e) = t.set(key, value.as_str()) {
+                        errors::handle(&format!("{}{}", errors::LUA, e));
+                        panic!();
+                    }
+                }
+                for (key, value) in [("position", self.position), ("length", self.length)] {
+                    if let Err(e) = t.set(key, value) {
+                        errors::handle(&format!("{}{}", errors::LUA, e));
+                        panic!();
+                    }
+                }
+                if let Err(e) = lua.globals().set("media", t) {
+                    errors::handle(&format!("{}{}", errors::LUA, e));
+                    panic!();
+                }
+            }
+            Err(e) => {
+                errors::handle(&format!("{}{}", errors::LUA, e));
+                panic!();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use dbus::arg::Variant;
+
+    fn metadata(entries: Vec<(&str, Box<dyn RefArg>)>) -> PropMap {
+        entries
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), Variant(value)))
+            .collect()
+    }
+
+    #[test]
+    fn reads_mpris_metadata() {
+        let metadata = metadata(vec![
+            ("xesam:title", Box::new(String::from("Song"))),
+            ("xesam:album", Box::new(String::from("Album"))),
+            (
+                "xesam:artist",
+                Box::new(vec![String::from("One"), String::from("Two")]),
+            ),
+            ("mpris:length", Box::new(215_000_000i64)),
+        ]);
+        let media = Media::from_properties(
+            "org.mpris.MediaPlayer2.spotify",
+            String::from("Playing"),
+            &metadata,
+            Some(62_500_000),
+        );
+        assert_eq!(media.player, "spotify");
+        assert_eq!(media.title, "Song");
+        assert_eq!(media.album, "Album");
+        assert_eq!(media.artist, "One, Two");
+        assert_eq!(media.status, "Playing");
+        assert_eq!(media.position, 62);
+        assert_eq!(media.length, 215);
+    }
+
+    #[test]
+    fn reads_sparse_mpris_metadata() {
+        let metadata = metadata(vec![
+            ("xesam:title", Box::new(String::from("Stream"))),
+            ("mpris:length", Box::new(90_000_000u64)),
+        ]);
+        let media = Media::from_properties(
+            "org.mpris.MediaPlayer2.vlc.instance42",
+            String::from("Paused"),
+            &metadata,
+            None,
+        );
+        assert_eq!(media.player, "vlc.instance42");
+        assert_eq!(media.title, "Stream");
+        assert_eq!(media.artist, "");
+        assert_eq!(media.album, "");
+        assert_eq!(media.position, 0);
+        assert_eq!(media.length, 90);
+    }
+}
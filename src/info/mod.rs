@@ -2,47 +2,66 @@ use crate::assets;
 use crate::assets::defaults;
 use crate::errors;
 use crate::mlua;
-use crate::regex;
 use crate::sysinfo;
+use crate::utils::text;
 use mlua::prelude::*;
-use regex::Regex;
 use std::fs;
 use std::path::Path;
 // use std::process::Command;
 // use std::sync::OnceLock;
 use std::thread;
+use std::time::Instant;
 use sysinfo::SystemExt;
 
+pub(crate) mod audio;
+pub(crate) mod bios;
 pub(crate) mod context;
 pub(crate) mod cpu;
 pub(crate) mod de;
 pub(crate) mod distro;
+pub(crate) mod drives;
 pub(crate) mod gpu;
 pub(crate) mod host;
 pub(crate) mod kernel;
+pub(crate) mod lines;
+pub(crate) mod load;
+pub(crate) mod locale;
+#[cfg(feature = "media")]
+pub(crate) mod media;
 pub(crate) mod memory;
 pub(crate) mod motherboard;
 pub(crate) mod package_managers;
 pub(crate) mod resolution;
+pub(crate) mod sensors;
 pub(crate) mod shell;
 pub(crate) mod uptime;
 pub(crate) mod utils;
 pub(crate) mod wm;
 
+use crate::color::ColorSupport;
 use crate::Inject;
 use assets::{ANSI, PRINT};
+use audio::Audio;
+use bios::Bios;
 use context::Context;
 use cpu::Cpu;
 use de::De;
 use defaults::INFO;
 use distro::Distro;
+use drives::Drives;
 use gpu::Gpus;
 use host::Host;
 use kernel::Kernel;
+use lines::Lines;
+use load::Load;
+use locale::Locale;
+#[cfg(feature = "media")]
+use media::Media;
 use memory::Memory;
 use motherboard::Motherboard;
 use package_managers::PackageManagers;
 use resolution::Resolution;
+use sensors::Sensors;
 use shell::Shell;
 use uptime::Uptime;
 use utils::get_system;
@@ -82,8 +101,10 @@ fn fetch_parallel_info() -> (Option<Cpu>, Memory, Option<Gpus>) {
 pub(crate) struct Info {
     ctx: Lua,
     rendered: String,
-    width: i32,
-    height: i32,
+    /// An `info.lua` to run instead of the config dir's, from `--preset`.
+    pub script: Option<String>,
+    pub width: i32,
+    pub height: i32,
     pub context: Option<Context>,
     pub distro: Distro,
     pub kernel: Kernel,
@@ -96,13 +117,22 @@ pub(crate) struct Info {
     pub cpu: Option<Cpu>,
     pub gpu: Option<Gpus>,
     pub memory: Memory,
+    pub load: Option<Load>,
+    pub sensors: Option<Sensors>,
+    pub drives: Option<Drives>,
+    pub audio: Option<Audio>,
+    #[cfg(feature = "media")]
+    pub media: Option<Media>,
     pub motherboard: Option<Motherboard>,
+    pub bios: Option<Bios>,
     pub host: Option<Host>,
+    pub locale: Option<Locale>,
 }
 
 impl Info {
     pub fn new() -> Self {
         get_system().refresh_all();
+        let refreshed = Instant::now();
 
         let kernel = Kernel::new();
         let context = Context::new();
@@ -117,11 +147,18 @@ impl Info {
         let uptime = Uptime::new(&kernel);
         let package_managers = PackageManagers::new(&kernel);
         let motherboard = Motherboard::new(&kernel);
+        let bios = Bios::new(&kernel);
         let host = Host::new(&kernel);
+        let locale = Locale::new(&kernel);
+        let load = Load::new(&kernel, refreshed);
+        let sensors = Sensors::new(&kernel);
+        let drives = Drives::new(&kernel);
+        let audio = Audio::new(&kernel);
 
         Info {
             ctx: Lua::new(),
             rendered: String::new(),
+            script: None,
             width: 0,
             height: 0,
             context,
@@ -136,8 +173,16 @@ impl Info {
             cpu,
             gpu,
             memory,
-            motherboard: Some(motherboard.unwrap()),
-            host: Some(host.unwrap()),
+            load,
+            sensors,
+            drives,
+            audio,
+            #[cfg(feature = "media")]
+            media: Media::new(),
+            motherboard,
+            bios,
+            host,
+            locale,
         }
     }
 
@@ -151,37 +196,26 @@ impl Info {
             errors::handle(&format!("{}{}", errors::LUA, e));
             panic!();
         }
+        ColorSupport::current().inject(&mut self.ctx);
+        let lines = Lines::default();
+        if let Err(e) = lines.register(&self.ctx) {
+            errors::handle(&format!("{}{}", errors::LUA, e));
+            panic!();
+        }
 
         let info = Path::new("/home/")
             .join(
                 self.context
-                    .clone()
-                    .unwrap_or(Context {
-                        user: String::new(),
-                        host: String::new(),
-                    })
-                    .user,
+                    .as_ref()
+                    .map(|c| c.user.clone())
+                    .unwrap_or_default(),
             )
             .join(".config/freshfetch/info.lua");
 
-        if info.exists() {
-            match fs::read_to_string(&info) {
-                Ok(file) => {
-                    match self.ctx.load(&file).exec() {
-                        Ok(_) => (),
-                        Err(e) => {
-                            errors::handle(&format!("{}{}", errors::LUA, e));
-                            panic!();
-                        }
-                    }
-                    match self.ctx.globals().get::<&str, String>("__freshfetch__") {
-                        Ok(v) => self.rendered = v,
-                        Err(e) => {
-                            errors::handle(&format!("{}{}", errors::LUA, e));
-                            panic!();
-                        }
-                    }
-                }
+        let script = match &self.script {
+            Some(v) => v.clone(),
+            None if info.exists() => match fs::read_to_string(&info) {
+                Ok(v) => v,
                 Err(e) => {
                     errors::handle(&format!(
                         "{}{file:?}{}{err}",
@@ -192,21 +226,21 @@ impl Info {
                     ));
                     panic!();
                 }
+            },
+            None => INFO.to_string(),
+        };
+        match self.ctx.load(&script).exec() {
+            Ok(_) => (),
+            Err(e) => {
+                errors::handle(&format!("{}{}", errors::LUA, e));
+                panic!();
             }
-        } else {
-            match self.ctx.load(INFO).exec() {
-                Ok(_) => (),
-                Err(e) => {
-                    errors::handle(&format!("{}{}", errors::LUA, e));
-                    panic!();
-                }
-            }
-            match self.ctx.globals().get::<&str, String>("__freshfetch__") {
-                Ok(v) => self.rendered = v,
-                Err(e) => {
-                    errors::handle(&format!("{}{}", errors::LUA, e));
-                    panic!();
-                }
+        }
+        match self.ctx.globals().get::<&str, String>("__freshfetch__") {
+            Ok(v) => self.rendered = lines.resolve(&v),
+            Err(e) => {
+                errors::handle(&format!("{}{}", errors::LUA, e));
+                panic!();
             }
         }
     }
@@ -240,33 +274,39 @@ impl Inject for Info {
             v.inject(&mut self.ctx);
         }
         self.memory.inject(&mut self.ctx);
+        if let Some(v) = &self.load {
+            v.inject(&mut self.ctx);
+        }
+        if let Some(v) = &self.sensors {
+            v.inject(&mut self.ctx);
+        }
+        if let Some(v) = &self.drives {
+            v.inject(&mut self.ctx);
+        }
+        if let Some(v) = &self.audio {
+            v.inject(&mut self.ctx);
+        }
+        #[cfg(feature = "media")]
+        if let Some(v) = &self.media {
+            v.inject(&mut self.ctx);
+        }
 
         if let Some(v) = &self.motherboard {
             v.inject(&mut self.ctx);
         }
+        if let Some(v) = &self.bios {
+            v.inject(&mut self.ctx);
+        }
         if let Some(v) = &self.host {
             v.inject(&mut self.ctx);
         }
+        if let Some(v) = &self.locale {
+            v.inject(&mut self.ctx);
+        }
 
         self.render();
 
-        // Strip ANSI codes and compute width & height
-        let plaintext = {
-            let regex = Regex::new(r#"(?i)\x1b\[[\d;]*[a-zA-Z]"#).unwrap();
-            regex.replace_all(&self.rendered, "").to_string()
-        };
-
-        let mut w = 0usize;
-        let mut h = 0usize;
-
-        for line in plaintext.lines() {
-            let len = line.chars().count();
-            if len > w {
-                w = len;
-            }
-            h += 1;
-        }
-
+        let (w, h) = text::measure(&self.rendered);
         self.width = w as i32;
         self.height = h as i32;
     }
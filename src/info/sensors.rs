@@ -0,0 +1,359 @@
+use crate::mlua;
+
+use super::kernel;
+use super::utils::read_trimmed;
+use crate::errors;
+
+use std::fs;
+use std::path::Path;
+
+use mlua::prelude::*;
+
+use crate::Inject;
+use kernel::Kernel;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum SensorKind {
+    /// Degrees Celsius.
+    Temperature,
+    /// Revolutions per minute.
+    Fan,
+    /// Volts.
+    Voltage,
+}
+
+impl SensorKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            SensorKind::Temperature => "temperature",
+            SensorKind::Fan => "fan",
+            SensorKind::Voltage => "voltage",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct Sensor {
+    pub chip: String,
+    pub class: &'static str,
+    pub kind: SensorKind,
+    pub label: String,
+    pub value: f64,
+    pub critical: Option<f64>,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct Sensors(pub Vec<Sensor>);
+
+impl Sensors {
+    pub fn new(k: &Kernel) -> Option<Self> {
+        if k.name != "Linux" {
+            return None;
+        }
+        let sensors = Self::from_sysfs(Path::new("/sys/class"));
+        if sensors.0.is_empty() {
+            None
+        } else {
+            Some(sensors)
+        }
+    }
+
+    /// Reads every hwmon sensor and thermal zone below `root`, which is
+    /// normally `/sys/class`.
+    pub fn from_sysfs(root: &Path) -> Self {
+        let mut sensors = Vec::new();
+
+        for chip_dir in Self::sorted_entries(&root.join("hwmon"), "hwmon") {
+            // Some drivers put their attributes in `device/` instead.
+            let dir = if chip_dir.join("name").is_file() {
+                chip_dir
+            } else {
+                chip_dir.join("device")
+            };
+            let chip = read_trimmed(dir.join("name")).unwrap_or_default();
+
+            for (prefix, kind, divisor) in [
+                ("temp", SensorKind::Temperature, 1000.0),
+                ("fan", SensorKind::Fan, 1.0),
+                ("in", SensorKind::Voltage, 1000.0),
+            ] {
+                for n in Self::channels(&dir, prefix) {
+                    let read = |attr: &str| {
+                        read_trimmed(dir.join(format!("{}{}_{}", prefix, n, attr)))
+                            .and_then(|v| v.parse::<f64>().ok())
+                            .map(|v| v / divisor)
+                    };
+                    let Some(value) = read("input") else {
+                        continue;
+                    };
+                    let label = read_trimmed(dir.join(format!("{}{}_label", prefix, n)))
+                        .unwrap_or_else(|| format!("{}{}", prefix, n));
+                    sensors.push(Sensor {
+                        class: classify(&chip, &label),
+                        chip: chip.clone(),
+                        kind,
+                        label,
+                        value,
+                        critical: read("crit"),
+                    });
+                }
+            }
+        }
+
+        for zone in Self::sorted_entries(&root.join("thermal"), "thermal_zone") {
+            let Some(value) = read_trimmed(zone.join("temp"))
+                .and_then(|v| v.parse::<f64>().ok())
+                .map(|v| v / 1000.0)
+            else {
+                continue;
+            };
+            let chip = read_trimmed(zone.join("type")).unwrap_or_default();
+            sensors.push(Sensor {
+                class: classify(&chip, &chip),
+                label: chip.clone(),
+                chip,
+                kind: SensorKind::Temperature,
+                value,
+                critical: None,
+            });
+        }
+
+        Sensors(sensors)
+    }
+
+    /// Picks the temperature that best represents the CPU package, the way
+    /// `sensors` users would eyeball it.
+    pub fn main_cpu_temp(&self) -> Option<f64> {
+        let cpu_temps = self
+            .0
+            .iter()
+            .filter(|s| s.kind == SensorKind::Temperature && s.class == "cpu")
+            .collect::<Vec<&Sensor>>();
+
+        for preferred in ["Package id 0", "Tctl", "Tdie", "CPU"] {
+            if let Some(s) = cpu_temps.iter().find(|s| s.label == preferred) {
+                return Some(s.value);
+            }
+        }
+        if let Some(s) = cpu_temps.first() {
+            return Some(s.value);
+        }
+        self.0
+            .iter()
+            .find(|s| s.kind == SensorKind::Temperature && s.class == "acpi")
+            .map(|s| s.value)
+    }
+
+    fn table<'lua>(&self, lua: &'lua Lua) -> LuaResult<LuaTable<'lua>> {
+        let t = lua.create_table()?;
+        for (i, sensor) in self.0.iter().enumerate() {
+            let s = lua.create_table()?;
+            s.set("chip", sensor.chip.as_str())?;
+            s.set("class", sensor.class)?;
+            s.set("kind", sensor.kind.as_str())?;
+            s.set("label", sensor.label.as_str())?;
+            s.set("value", sensor.value)?;
+            s.set("critical", sensor.critical)?;
+            t.raw_set((i + 1) as i64, s)?;
+        }
+        t.set("cpuTemp", self.main_cpu_temp())?;
+        Ok(t)
+    }
+
+    fn sorted_entries(dir: &Path, prefix: &str) -> Vec<std::path::PathBuf> {
+        let mut entries = fs::read_dir(dir)
+            .map(|d| {
+                d.flatten()
+                    .filter(|e| e.file_name().to_string_lossy().starts_with(prefix))
+                    .map(|e| e.path())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        // Sort numerically so hwmon10 comes after hwmon9.
+        entries.sort_by_key(|p| {
+            p.file_name()
+                .map(|n| n.to_string_lossy().trim_start_matches(prefix).to_string())
+                .and_then(|n| n.parse::<u32>().ok())
+                .unwrap_or(u32::MAX)
+        });
+        entries
+    }
+
+    /// Returns the channel numbers that have a `<prefix>N_input` file.
+    fn channels(dir: &Path, prefix: &str) -> Vec<u32> {
+        let mut channels = fs::read_dir(dir)
+            .map(|d| {
+                d.flatten()
+                    .filter_map(|e| {
+                        let name = e.file_name().to_string_lossy().to_string();
+                        name.strip_prefix(prefix)?
+                            .strip_suffix("_input")?
+                            .parse::<u32>()
+                            .ok()
+                    })
+                    .collect::<Vec<u32>>()
+            })
+            .unwrap_or_default();
+        channels.sort_unstable();
+        channels
+    }
+}
+
+/// Sorts a sensor into a coarse class based on its driver name and label.
+fn classify(chip: &str, label: &str) -> &'static str {
+    let chip = chip.to_lowercase();
+    let label = label.to_lowercase();
+
+    match chip.as_str() {
+        "coretemp" | "k8temp" | "k10temp" | "zenpower" | "via_cputemp" | "cpu_thermal"
+        | "cpu-thermal" | "x86_pkg_temp" | "soc_thermal" => "cpu",
+        "amdgpu" | "radeon" | "nouveau" | "i915" | "xe" | "nvidia" | "gpu_thermal" => "gpu",
+        "nvme" => "nvme",
+        "drivetemp" => "disk",
+        "acpitz" => "acpi",
+        c if c.starts_with("pch_") || c == "chipset" => "chipset",
+        c if c.starts_with("iwlwifi") || c.starts_with("ath") || c.starts_with("mt76") => "wifi",
+        c if c.starts_with("bat") || c == "battery" => "battery",
+        // Super I/O and laptop EC chips expose a mix of sensors, so fall
+        // back to the label.
+        _ if label.contains("cpu") || label.contains("package") => "cpu",
+        _ if label.contains("gpu") => "gpu",
+        _ if label.contains("pch") || label.contains("chipset") => "chipset",
+        c if c.starts_with("nct")
+            || c.starts_with("it8")
+            || c.starts_with("w83")
+            || c.starts_with("asus")
+            || c == "dell_smm"
+            || c == "thinkpad" =>
+        {
+            "board"
+        }
+        _ => "other",
+    }
+}
+
+impl Inject for Sensors {
+    fn inject(&self, lua: &mut Lua) {
+        match self
+            .table(lua)
+            .and_then(|t| lua.globals().set("sensors", t))
+        {
+            Ok(_) => (),
+            Err(e) => {
+                errors::handle(&format!("{}{}", errors::LUA, e));
+                panic!();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::path::PathBuf;
+
+    /// A fake `/sys/class` in the temp dir, removed when dropped.
+    struct FakeSysfs(PathBuf);
+
+    impl FakeSysfs {
+        fn new(name: &str) -> Self {
+            let root = std::env::temp_dir().join(format!(
+                "freshfetch-sensors-{}-{}",
+                name,
+                std::process::id()
+            ));
+            let _ = fs::remove_dir_all(&root);
+            FakeSysfs(root)
+        }
+
+        fn file(&self, path: &str, contents: &str) -> &Self {
+            let path = self.0.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+            self
+        }
+    }
+
+    impl Drop for FakeSysfs {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn find<'a>(sensors: &'a Sensors, chip: &str, label: &str) -> &'a Sensor {
+        sensors
+            .0
+            .iter()
+            .find(|s| s.chip == chip && s.label == label)
+            .unwrap_or_else(|| panic!("no {} {}", chip, label))
+    }
+
+    #[test]
+    fn classifies_amd_nvme_and_amdgpu() {
+        let sysfs = FakeSysfs::new("amd");
+        sysfs
+            .file("hwmon/hwmon0/name", "k10temp\n")
+            .file("hwmon/hwmon0/temp1_input", "45125\n")
+            .file("hwmon/hwmon0/temp1_label", "Tctl\n")
+            .file("hwmon/hwmon0/temp3_input", "40000\n")
+            .file("hwmon/hwmon0/temp3_label", "Tccd1\n")
+            .file("hwmon/hwmon1/name", "nvme\n")
+            .file("hwmon/hwmon1/temp1_input", "38850\n")
+            .file("hwmon/hwmon1/temp1_label", "Composite\n")
+            .file("hwmon/hwmon1/temp1_crit", "84850\n")
+            // amdgpu keeps its attributes under `device/`.
+            .file("hwmon/hwmon10/device/name", "amdgpu\n")
+            .file("hwmon/hwmon10/device/temp1_input", "52000\n")
+            .file("hwmon/hwmon10/device/temp1_label", "edge\n")
+            .file("hwmon/hwmon10/device/fan1_input", "1200\n")
+            .file("hwmon/hwmon10/device/in0_input", "800\n")
+            .file("thermal/thermal_zone0/type", "acpitz\n")
+            .file("thermal/thermal_zone0/temp", "30000\n");
+        let sensors = Sensors::from_sysfs(&sysfs.0);
+
+        assert_eq!(sensors.0.len(), 7);
+        assert_eq!(find(&sensors, "k10temp", "Tccd1").class, "cpu");
+        let nvme = find(&sensors, "nvme", "Composite");
+        assert_eq!(nvme.class, "nvme");
+        assert_eq!(nvme.value, 38.85);
+        assert_eq!(nvme.critical, Some(84.85));
+        assert_eq!(find(&sensors, "amdgpu", "edge").class, "gpu");
+        let fan = find(&sensors, "amdgpu", "fan1");
+        assert_eq!((fan.kind, fan.value), (SensorKind::Fan, 1200.0));
+        let voltage = find(&sensors, "amdgpu", "in0");
+        assert_eq!((voltage.kind, voltage.value), (SensorKind::Voltage, 0.8));
+        assert_eq!(find(&sensors, "acpitz", "acpitz").class, "acpi");
+        // hwmon10 comes after hwmon1, not between hwmon1 and hwmon2.
+        assert_eq!(sensors.0.last().map(|s| s.chip.as_str()), Some("acpitz"));
+        assert_eq!(sensors.0[5].chip, "amdgpu");
+
+        assert_eq!(sensors.main_cpu_temp(), Some(45.125));
+    }
+
+    #[test]
+    fn prefers_the_intel_package_temperature() {
+        let sysfs = FakeSysfs::new("intel");
+        sysfs
+            .file("hwmon/hwmon2/name", "coretemp\n")
+            .file("hwmon/hwmon2/temp2_input", "51000\n")
+            .file("hwmon/hwmon2/temp2_label", "Core 0\n")
+            .file("hwmon/hwmon2/temp1_input", "55000\n")
+            .file("hwmon/hwmon2/temp1_label", "Package id 0\n");
+        let sensors = Sensors::from_sysfs(&sysfs.0);
+
+        assert!(sensors.0.iter().all(|s| s.class == "cpu"));
+        assert_eq!(sensors.main_cpu_temp(), Some(55.0));
+    }
+
+    #[test]
+    fn falls_back_to_acpi() {
+        let sysfs = FakeSysfs::new("acpi");
+        sysfs
+            .file("thermal/thermal_zone0/type", "acpitz\n")
+            .file("thermal/thermal_zone0/temp", "27800\n");
+        let sensors = Sensors::from_sysfs(&sysfs.0);
+
+        assert_eq!(sensors.main_cpu_temp(), Some(27.8));
+    }
+}
@@ -1,5 +1,8 @@
 use crate::sysinfo;
 
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
 use std::sync::{Mutex, MutexGuard};
 
 use sysinfo::{ProcessExt, System, SystemExt};
@@ -12,6 +15,45 @@ pub(crate) fn get_system() -> MutexGuard<'static, System> {
     SYSTEM.lock().unwrap()
 }
 
+/// Reads a small text file (usually from sysfs or procfs), returning its
+/// trimmed contents, or `None` if it can't be read or is empty.
+pub(crate) fn read_trimmed<P: AsRef<Path>>(path: P) -> Option<String> {
+    let contents = fs::read_to_string(path).ok()?;
+    let trimmed = contents.trim();
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed.to_string())
+    }
+}
+
+/// Parses a shell-style `KEY=value` file such as `/etc/os-release` or
+/// `/etc/vconsole.conf`. Comments, `export` prefixes and surrounding quotes
+/// are stripped; no variable expansion is done.
+pub(crate) fn parse_env_file(contents: &str) -> HashMap<String, String> {
+    let mut to_return = HashMap::new();
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            let key = key.trim().trim_start_matches("export ").trim();
+            let value = value.trim();
+            let value = if value.len() >= 2
+                && ((value.starts_with('"') && value.ends_with('"'))
+                    || (value.starts_with('\'') && value.ends_with('\'')))
+            {
+                &value[1..value.len() - 1]
+            } else {
+                value
+            };
+            to_return.insert(key.to_string(), value.replace("\\\"", "\""));
+        }
+    }
+    to_return
+}
+
 #[derive(Clone, Debug)]
 pub(crate) struct Grep {
     pub max: Option<usize>,
@@ -69,3 +111,30 @@ impl PsAux {
         grep(self.0.clone(), conf)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_env_files() {
+        let env = parse_env_file(
+            "# comment\n\
+             \n\
+             NAME=\"Arch Linux\"\n\
+             export KEYMAP=de-latin1\n\
+             \x20 FONT = 'ter-116n'  \n\
+             PRETTY_NAME=\"Say \\\"hi\\\"\"\n\
+             EMPTY=\n\
+             QUOTE=\"\n\
+             not a variable\n",
+        );
+        assert_eq!(env["NAME"], "Arch Linux");
+        assert_eq!(env["KEYMAP"], "de-latin1");
+        assert_eq!(env["FONT"], "ter-116n");
+        assert_eq!(env["PRETTY_NAME"], "Say \"hi\"");
+        assert_eq!(env["EMPTY"], "");
+        assert_eq!(env["QUOTE"], "\"");
+        assert_eq!(env.len(), 6);
+    }
+}
@@ -0,0 +1,370 @@
+use crate::mlua;
+
+use crate::utils::text;
+
+use mlua::prelude::*;
+
+/// Where the art goes relative to the info.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Placement {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+impl Placement {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "left" => Some(Placement::Left),
+            "right" => Some(Placement::Right),
+            "top" => Some(Placement::Top),
+            "bottom" => Some(Placement::Bottom),
+            _ => None,
+        }
+    }
+
+    fn side_by_side(&self) -> bool {
+        matches!(self, Placement::Left | Placement::Right)
+    }
+}
+
+/// What to do with info lines too long to fit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Overflow {
+    /// Cut the line off with an ellipsis.
+    Truncate,
+    /// Continue the line on the next one.
+    Wrap,
+}
+
+impl Overflow {
+    /// Markers that pick the overflow for a single line of the info, made
+    /// by the `wrap()` and `truncate()` Lua helpers. freshfetch's markers
+    /// are all APC strings, `ESC _ freshfetch:... ESC \`, which terminals
+    /// don't show.
+    const WRAP: &'static str = "\u{001b}_freshfetch:wrap\u{001b}\\";
+    const TRUNCATE: &'static str = "\u{001b}_freshfetch:truncate\u{001b}\\";
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "truncate" => Some(Overflow::Truncate),
+            "wrap" => Some(Overflow::Wrap),
+            _ => None,
+        }
+    }
+
+    /// Takes the markers out of `line`, returning what's left and the
+    /// overflow the line asked for, or `self` if it didn't.
+    fn for_line(&self, line: &str) -> (String, Overflow) {
+        let overflow = if line.contains(Self::WRAP) {
+            Overflow::Wrap
+        } else if line.contains(Self::TRUNCATE) {
+            Overflow::Truncate
+        } else {
+            *self
+        };
+        (
+            line.replace(Self::WRAP, "").replace(Self::TRUNCATE, ""),
+            overflow,
+        )
+    }
+}
+
+/// Takes out the markers `compose` didn't get to, like when a layout prints
+/// `info` on its own.
+pub(crate) fn strip_markers(s: &str) -> String {
+    s.replace(Overflow::WRAP, "")
+        .replace(Overflow::TRUNCATE, "")
+}
+
+/// Where the shorter of the art and info sits when they're side by side.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum VAlign {
+    Top,
+    Center,
+    Bottom,
+}
+
+impl VAlign {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "top" => Some(VAlign::Top),
+            "center" => Some(VAlign::Center),
+            "bottom" => Some(VAlign::Bottom),
+            _ => None,
+        }
+    }
+
+    /// Blank lines to put before a block `height` lines tall so it lines up
+    /// in `total` lines.
+    fn offset(&self, height: usize, total: usize) -> usize {
+        match self {
+            VAlign::Top => 0,
+            VAlign::Center => (total - height) / 2,
+            VAlign::Bottom => total - height,
+        }
+    }
+}
+
+pub(crate) struct Compose<'a> {
+    pub art: &'a str,
+    /// The size of the art in cells, when it's known better than measuring
+    /// `art` can tell. Images drawn with escapes measure as nothing.
+    pub art_width: Option<usize>,
+    pub art_height: Option<usize>,
+    pub info: &'a str,
+    /// Columns between the art and info when they're side by side, or lines
+    /// when one is above the other.
+    pub gap: usize,
+    /// Columns before every line.
+    pub margin: usize,
+    pub placement: Placement,
+    pub valign: VAlign,
+    /// The most columns a line can take up, if there's a limit.
+    pub width: Option<usize>,
+    /// What to do with info lines that go past `width`.
+    pub overflow: Overflow,
+}
+
+/// A block of text split into lines that can each be printed on their own,
+/// and the width of the widest one.
+struct Block {
+    lines: Vec<String>,
+    width: usize,
+}
+
+impl Block {
+    fn new(s: &str) -> Self {
+        Self::from_lines(text::carry_sgr(s.trim_end_matches('\n')))
+    }
+
+    /// Like `new`, but with lines wider than `width` truncated or wrapped.
+    fn fitted(s: &str, width: Option<usize>, overflow: Overflow) -> Self {
+        let mut lines = Vec::new();
+        for line in text::carry_sgr(s.trim_end_matches('\n')) {
+            let (line, overflow) = overflow.for_line(&line);
+            match width {
+                Some(width) if text::display_width(&line) > width => match overflow {
+                    Overflow::Truncate => lines.push(text::truncate(&line, width)),
+                    Overflow::Wrap => lines.extend(text::wrap(&line, width)),
+                },
+                _ => lines.push(line),
+            }
+        }
+        Self::from_lines(lines)
+    }
+
+    fn from_lines(lines: Vec<String>) -> Self {
+        let width = lines
+            .iter()
+            .map(|l| text::display_width(l))
+            .max()
+            .unwrap_or(0);
+        Block { lines, width }
+    }
+
+    /// The `i`th line padded to the block's width, or blank if the block
+    /// isn't that tall.
+    fn padded(&self, i: Option<usize>) -> String {
+        match i.and_then(|i| self.lines.get(i)) {
+            Some(line) => format!(
+                "{}{}",
+                line,
+                " ".repeat(self.width.saturating_sub(text::display_width(line)))
+            ),
+            None => " ".repeat(self.width),
+        }
+    }
+}
+
+impl<'a> Compose<'a> {
+    /// Puts the art and info together line by line, without moving the
+    /// cursor, so the result can be printed anywhere. Info lines that would
+    /// go past `width` are truncated or wrapped; the art is left alone.
+    pub fn render(&self) -> String {
+        let mut art = Block::new(self.art);
+        if let Some(width) = self.art_width {
+            art.width = width;
+        }
+        if let Some(height) = self.art_height {
+            let height = height.max(art.lines.len());
+            art.lines.resize(height, String::new());
+        }
+        let available = self.width.map(|width| {
+            let used = if self.placement.side_by_side() {
+                self.margin + art.width + self.gap
+            } else {
+                self.margin
+            };
+            width.saturating_sub(used)
+        });
+        let info = Block::fitted(self.info, available, self.overflow);
+        let margin = " ".repeat(self.margin);
+
+        let mut to_return = Vec::new();
+        if self.placement.side_by_side() {
+            let (first, second) = match self.placement {
+                Placement::Left => (&art, &info),
+                _ => (&info, &art),
+            };
+            let height = art.lines.len().max(info.lines.len());
+            let first_offset = self.valign.offset(first.lines.len(), height);
+            let second_offset = self.valign.offset(second.lines.len(), height);
+            for i in 0..height {
+                let line = format!(
+                    "{}{}{}{}",
+                    margin,
+                    first.padded(i.checked_sub(first_offset)),
+                    " ".repeat(self.gap),
+                    second.padded(i.checked_sub(second_offset)),
+                );
+                to_return.push(line.trim_end().to_string());
+            }
+        } else {
+            let (first, second) = match self.placement {
+                Placement::Top => (&art, &info),
+                _ => (&info, &art),
+            };
+            for line in first.lines.iter() {
+                to_return.push(format!("{}{}", margin, line));
+            }
+            to_return.extend((0..self.gap).map(|_| String::new()));
+            for line in second.lines.iter() {
+                to_return.push(format!("{}{}", margin, line));
+            }
+        }
+        to_return.join("\n")
+    }
+}
+
+/// Adds `compose{art=..., artWidth=..., artHeight=..., info=..., gap=...,
+/// margin=..., align=..., valign=..., width=..., overflow=...}`, which
+/// renders a `Compose`. `artWidth` and `artHeight` are the art's size in
+/// cells, and are measured from `art` when left out. `align` is where the
+/// art goes: `"left"` (the default), `"right"`, `"top"` or `"bottom"`.
+/// `valign` is `"top"` (the default), `"center"` or `"bottom"`. `width`
+/// defaults to `terminal_width`, and `0` means no limit. `overflow` is
+/// `"truncate"` (the default) or `"wrap"`.
+pub(crate) fn register(lua: &Lua, terminal_width: i32) -> LuaResult<()> {
+    let compose = lua.create_function(move |_, options: LuaTable| {
+        let art = options.get::<_, Option<String>>("art")?.unwrap_or_default();
+        let art_width = options.get::<_, Option<usize>>("artWidth")?;
+        let art_height = options.get::<_, Option<usize>>("artHeight")?;
+        let info = options
+            .get::<_, Option<String>>("info")?
+            .unwrap_or_default();
+
+        let placement = match options.get::<_, Option<String>>("align")? {
+            Some(name) => Placement::from_name(&name).ok_or_else(|| {
+                LuaError::RuntimeError(format!("\"{}\" isn't a valid align", name))
+            })?,
+            None => Placement::Left,
+        };
+        let valign = match options.get::<_, Option<String>>("valign")? {
+            Some(name) => VAlign::from_name(&name).ok_or_else(|| {
+                LuaError::RuntimeError(format!("\"{}\" isn't a valid valign", name))
+            })?,
+            None => VAlign::Top,
+        };
+        let gap = options
+            .get::<_, Option<usize>>("gap")?
+            .unwrap_or(if placement.side_by_side() { 2 } else { 1 });
+        let margin = options.get::<_, Option<usize>>("margin")?.unwrap_or(0);
+        let width = match options.get::<_, Option<usize>>("width")? {
+            Some(0) => None,
+            Some(width) => Some(width),
+            None if terminal_width > 0 => Some(terminal_width as usize),
+            None => None,
+        };
+        let overflow = match options.get::<_, Option<String>>("overflow")? {
+            Some(name) => Overflow::from_name(&name).ok_or_else(|| {
+                LuaError::RuntimeError(format!("\"{}\" isn't a valid overflow", name))
+            })?,
+            None => Overflow::Truncate,
+        };
+
+        Ok(Compose {
+            art: &art,
+            art_width,
+            art_height,
+            info: &info,
+            gap,
+            margin,
+            placement,
+            valign,
+            width,
+            overflow,
+        }
+        .render())
+    })?;
+    lua.globals().set("compose", compose)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compose<'a>(art: &'a str, info: &'a str) -> Compose<'a> {
+        Compose {
+            art,
+            art_width: None,
+            art_height: None,
+            info,
+            gap: 2,
+            margin: 0,
+            placement: Placement::Left,
+            valign: VAlign::Top,
+            width: None,
+            overflow: Overflow::Truncate,
+        }
+    }
+
+    #[test]
+    fn strips_leftover_markers() {
+        let info = format!("a{}\nb{}", Overflow::WRAP, Overflow::TRUNCATE);
+        assert_eq!(strip_markers(&info), "a\nb");
+    }
+
+    #[test]
+    fn measures_text_art() {
+        let rendered = compose("ab\nabcd", "x\ny").render();
+        assert_eq!(text::strip_ansi(&rendered), "ab    x\nabcd  y");
+    }
+
+    #[test]
+    fn image_art_uses_the_given_size() {
+        // Like the kitty backend: the image is all escapes, then padding.
+        let image = "\u{001b}_Ga=T\u{001b}\\\n\n";
+        let rendered = Compose {
+            art_width: Some(4),
+            art_height: Some(3),
+            ..compose(image, "a\nb")
+        }
+        .render();
+        assert!(rendered.starts_with("\u{001b}_Ga=T\u{001b}\\"));
+        assert_eq!(text::strip_ansi(&rendered), "      a\n      b\n");
+    }
+
+    #[test]
+    fn leaves_long_lines_alone_without_a_terminal() {
+        let run = |terminal_width: i32| -> String {
+            let lua = Lua::new();
+            register(&lua, terminal_width).unwrap();
+            lua.globals()
+                .set("long", format!("{}{}", "x".repeat(30), Overflow::WRAP))
+                .unwrap();
+            let rendered: String = lua
+                .load(r#"return compose{art = "ab\nab", info = "short\n" .. long}"#)
+                .eval()
+                .unwrap();
+            text::strip_ansi(&rendered)
+        };
+        let long = "x".repeat(30);
+        assert_eq!(run(0), format!("ab  short\nab  {}", long));
+        assert_eq!(
+            run(20),
+            format!("ab  short\nab  {}\n    {}", &long[..16], &long[16..])
+        );
+    }
+}
@@ -1,6 +1,9 @@
+pub(crate) mod compose;
+
 use crate::mlua;
 
 use crate::art;
+use crate::errors;
 use crate::info;
 use crate::misc;
 
@@ -11,6 +14,10 @@ use art::Art;
 use info::Info;
 use misc::Terminal;
 
+/// Columns the default layout puts around the art: two before it and two
+/// between it and the info.
+const ART_PADDING: i32 = 4;
+
 pub(crate) struct Layout {
     pub art: Art,
     pub info: Info,
@@ -20,8 +27,9 @@ pub(crate) struct Layout {
 impl Layout {
     pub fn new(args: &Arguments) -> Self {
         let mut info = Info::new();
-        let art = Art::new(&mut info, args);
+        info.script = args.preset.as_ref().and_then(|p| p.info.clone());
         let terminal = Terminal::new();
+        let art = Art::new(&mut info, args, &terminal);
         Layout {
             art,
             info,
@@ -34,9 +42,17 @@ impl Inject for Layout {
     fn prep(&mut self) {
         self.info.prep();
         self.art.prep();
+        if self.terminal.width > 0 {
+            self.art
+                .fit(self.terminal.width - self.info.width - ART_PADDING);
+        }
         self.terminal.prep();
     }
     fn inject(&self, lua: &mut Lua) {
+        if let Err(e) = compose::register(lua, self.terminal.width) {
+            errors::handle(&format!("{}{}", errors::LUA, e));
+            panic!();
+        }
         self.art.inject(lua);
         self.terminal.inject(lua);
         self.info.inject(lua);
@@ -1,29 +1,42 @@
 #[macro_use]
 pub(crate) extern crate lazy_static;
+pub(crate) extern crate base64;
 pub(crate) extern crate chrono;
 pub(crate) extern crate clap;
+pub(crate) extern crate clml_rs;
 pub(crate) extern crate cmd_lib;
+#[cfg(feature = "media")]
+pub(crate) extern crate dbus;
+pub(crate) extern crate image;
+pub(crate) extern crate libc;
 pub(crate) extern crate mlua;
 pub(crate) extern crate regex;
 pub(crate) extern crate sysinfo;
 pub(crate) extern crate term_size;
 pub(crate) extern crate uname;
+pub(crate) extern crate unicode_width;
 pub(crate) extern crate users;
 
 pub(crate) mod art;
 pub(crate) mod assets;
+pub(crate) mod color;
+pub(crate) mod config;
 pub(crate) mod errors;
 pub(crate) mod info;
 pub(crate) mod layout;
 pub(crate) mod misc;
+pub(crate) mod presets;
 pub(crate) mod utils;
 
-use clap::{App, Arg};
+use clap::{App, AppSettings, Arg, SubCommand};
 use mlua::prelude::*;
 
 use assets::defaults::LAYOUT;
 use assets::{ANSI, HELP, PRINT};
-use layout::Layout;
+use color::{ColorMode, ColorSupport};
+use config::Config;
+use layout::{compose, Layout};
+use presets::Preset;
 
 use std::env::var;
 use std::fs::read_to_string;
@@ -31,6 +44,14 @@ use std::path::Path;
 
 pub(crate) struct Arguments {
     pub ascii_distro: Option<String>,
+    pub ascii: Option<String>,
+    pub ascii_size: Option<String>,
+    pub image: Option<String>,
+    pub backend: Option<String>,
+    pub image_colors: Option<String>,
+    pub image_width: Option<u32>,
+    pub os_colors: bool,
+    pub preset: Option<Preset>,
 }
 
 pub(crate) trait Inject {
@@ -51,17 +72,168 @@ fn main() {
                 .takes_value(true)
                 .value_name("ASCII_DISTRO"),
         )
+        .arg(
+            Arg::with_name("ascii")
+                .long("ascii")
+                .takes_value(true)
+                .value_name("FILE"),
+        )
+        .arg(
+            Arg::with_name("ascii_size")
+                .long("ascii-size")
+                .takes_value(true)
+                .value_name("SIZE")
+                .possible_values(&["small", "large", "old", "auto"]),
+        )
+        .arg(
+            Arg::with_name("image")
+                .long("image")
+                .short("i")
+                .takes_value(true)
+                .value_name("IMAGE"),
+        )
+        .arg(
+            Arg::with_name("backend")
+                .long("backend")
+                .takes_value(true)
+                .value_name("BACKEND")
+                .possible_values(&["kitty", "sixel", "iterm", "halfblock", "braille", "ascii"]),
+        )
+        .arg(
+            Arg::with_name("image_colors")
+                .long("image-colors")
+                .takes_value(true)
+                .value_name("COLORS")
+                .possible_values(&["truecolor", "256", "16"]),
+        )
+        .arg(
+            Arg::with_name("image_width")
+                .long("image-width")
+                .takes_value(true)
+                .value_name("COLUMNS"),
+        )
+        .arg(
+            Arg::with_name("os_colors")
+                .long("os-colors")
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name("color")
+                .long("color")
+                .takes_value(true)
+                .value_name("WHEN")
+                .possible_values(&["auto", "always", "never"]),
+        )
+        .arg(
+            Arg::with_name("preset")
+                .long("preset")
+                .short("p")
+                .takes_value(true)
+                .value_name("PRESET"),
+        )
+        .arg(
+            Arg::with_name("list_ascii")
+                .long("list-ascii")
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name("preview")
+                .long("preview")
+                .takes_value(false)
+                .requires("list_ascii"),
+        )
         .arg(
             Arg::with_name("logo")
                 .long("logo")
                 .short("l")
                 .takes_value(false),
+        )
+        .subcommand(
+            SubCommand::with_name("art")
+                .about("Tools for working on the built-in art.")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(
+                    SubCommand::with_name("preview")
+                        .about("Shows the built-in art in a grid.")
+                        .arg(
+                            Arg::with_name("filter")
+                                .long("filter")
+                                .takes_value(true)
+                                .value_name("GLOB"),
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("presets")
+                .about("Tools for the built-in and user presets.")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(
+                    SubCommand::with_name("list").about("Lists every preset --preset can use."),
+                ),
         );
 
     let matches = app.get_matches();
 
+    color::init(
+        matches
+            .value_of("color")
+            .and_then(ColorMode::from_name)
+            .unwrap_or(ColorMode::Auto),
+    );
+
+    if let Some(matches) = matches
+        .subcommand_matches("art")
+        .and_then(|m| m.subcommand_matches("preview"))
+    {
+        art::preview::preview(matches.value_of("filter"), &misc::Terminal::new());
+        return;
+    }
+
+    if matches
+        .subcommand_matches("presets")
+        .and_then(|m| m.subcommand_matches("list"))
+        .is_some()
+    {
+        presets::list();
+        return;
+    }
+
+    if matches.is_present("list_ascii") {
+        art::list(matches.is_present("preview"));
+        return;
+    }
+
+    let config = Config::load();
     let args = Arguments {
         ascii_distro: matches.value_of("ascii_distro").map(|v| v.to_string()),
+        ascii: matches.value_of("ascii").map(|v| v.to_string()),
+        ascii_size: matches.value_of("ascii_size").map(|v| v.to_string()),
+        image: matches.value_of("image").map(|v| v.to_string()),
+        backend: matches.value_of("backend").map(|v| v.to_string()),
+        image_colors: matches.value_of("image_colors").map(|v| v.to_string()),
+        image_width: matches
+            .value_of("image_width")
+            .map(|v| match v.parse::<u32>() {
+                Ok(v) => v,
+                Err(e) => {
+                    errors::handle(&format!(
+                        "{}{}{}u32{}{}",
+                        errors::PARSE.0,
+                        v,
+                        errors::PARSE.1,
+                        errors::PARSE.2,
+                        e
+                    ));
+                    panic!();
+                }
+            }),
+        os_colors: matches.is_present("os_colors") || config.os_colors,
+        preset: matches.value_of("preset").map(|name| {
+            Preset::find(name).unwrap_or_else(|| {
+                errors::handle(&format!("{}{}{}", errors::PRESET.0, name, errors::PRESET.1));
+                panic!();
+            })
+        }),
     };
 
     let mut ctx = Lua::new();
@@ -79,6 +251,7 @@ fn main() {
             panic!();
         }
     }
+    ColorSupport::current().inject(&mut ctx);
 
     let mut layout = Layout::new(&args);
     layout.prep();
@@ -88,24 +261,10 @@ fn main() {
         .join(var("USER").unwrap_or_default())
         .join(".config/freshfetch/layout.lua");
 
-    if layout_file.exists() {
-        match read_to_string(&layout_file) {
-            Ok(v) => {
-                match ctx.load(&v).exec() {
-                    Ok(_) => (),
-                    Err(e) => {
-                        errors::handle(&format!("{}{}", errors::LUA, e));
-                        panic!();
-                    }
-                }
-                match ctx.globals().get::<&str, String>("__freshfetch__") {
-                    Ok(v) => print!("{}", v),
-                    Err(e) => {
-                        errors::handle(&format!("{}{}", errors::LUA, e));
-                        panic!();
-                    }
-                }
-            }
+    let script = match args.preset.as_ref().and_then(|p| p.layout.clone()) {
+        Some(v) => v,
+        None if layout_file.exists() => match read_to_string(&layout_file) {
+            Ok(v) => v,
             Err(e) => {
                 errors::handle(&format!(
                     "{}{file}{}{err}",
@@ -116,21 +275,21 @@ fn main() {
                 ));
                 panic!();
             }
+        },
+        None => LAYOUT.to_string(),
+    };
+    match ctx.load(&script).exec() {
+        Ok(_) => (),
+        Err(e) => {
+            errors::handle(&format!("{}{}", errors::LUA, e));
+            panic!();
         }
-    } else {
-        match ctx.load(LAYOUT).exec() {
-            Ok(_) => (),
-            Err(e) => {
-                errors::handle(&format!("{}{}", errors::LUA, e));
-                panic!();
-            }
-        }
-        match ctx.globals().get::<&str, String>("__freshfetch__") {
-            Ok(v) => print!("{}", v),
-            Err(e) => {
-                errors::handle(&format!("{}{}", errors::LUA, e));
-                panic!();
-            }
+    }
+    match ctx.globals().get::<&str, String>("__freshfetch__") {
+        Ok(v) => print!("{}", color::filter(&compose::strip_markers(&v))),
+        Err(e) => {
+            errors::handle(&format!("{}{}", errors::LUA, e));
+            panic!();
         }
     }
 }
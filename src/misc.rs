@@ -1,3 +1,4 @@
+use crate::libc;
 use crate::term_size;
 use crate::{errors, mlua, Inject};
 use mlua::prelude::*;
@@ -5,24 +6,52 @@ use mlua::prelude::*;
 pub(crate) struct Terminal {
     pub width: i32,
     pub height: i32,
+    /// Size of a single cell in pixels, or 0 if the terminal doesn't say.
+    pub cell_width: i32,
+    pub cell_height: i32,
 }
 
 impl Terminal {
     #[inline(always)]
     pub fn new() -> Self {
-        match term_size::dimensions() {
-            Some((w, h)) => Terminal {
-                width: w as i32,
-                height: h as i32,
-            },
-            None => {
-                errors::handle("Failed to get terminal dimensions.");
-                Terminal {
-                    width: 0,
-                    height: 0,
-                }
+        Self::from_dimensions(term_size::dimensions(), Self::cell_size())
+    }
+
+    /// Builds the terminal from its size in cells, if there's a terminal at
+    /// all. Output that's piped or redirected has no size, so the width and
+    /// height are left at 0, which everything else takes as "no limit".
+    fn from_dimensions(dimensions: Option<(usize, usize)>, cell_size: (i32, i32)) -> Self {
+        let (width, height) = dimensions.unwrap_or((0, 0));
+        Terminal {
+            width: width as i32,
+            height: height as i32,
+            cell_width: cell_size.0,
+            cell_height: cell_size.1,
+        }
+    }
+
+    /// Asks the terminal for its size in pixels with `TIOCGWINSZ` and
+    /// divides it by its size in cells. Plenty of terminals leave the pixel
+    /// fields at 0, in which case this returns `(0, 0)`.
+    fn cell_size() -> (i32, i32) {
+        for fd in [libc::STDOUT_FILENO, libc::STDIN_FILENO, libc::STDERR_FILENO] {
+            let mut size = libc::winsize {
+                ws_row: 0,
+                ws_col: 0,
+                ws_xpixel: 0,
+                ws_ypixel: 0,
+            };
+            // SAFETY: `size` is a valid `winsize` for the ioctl to fill in.
+            let ok = unsafe { libc::ioctl(fd, libc::TIOCGWINSZ, &mut size) } == 0;
+            if ok && size.ws_col > 0 && size.ws_row > 0 && size.ws_xpixel > 0 && size.ws_ypixel > 0
+            {
+                return (
+                    (size.ws_xpixel / size.ws_col) as i32,
+                    (size.ws_ypixel / size.ws_row) as i32,
+                );
             }
         }
+        (0, 0)
     }
 }
 
@@ -36,6 +65,12 @@ impl Inject for Terminal {
             let _ = table
                 .set("height", self.height)
                 .map_err(|e| errors::handle(&format!("{}{}", errors::LUA, e)));
+            let _ = table
+                .set("cellWidth", self.cell_width)
+                .map_err(|e| errors::handle(&format!("{}{}", errors::LUA, e)));
+            let _ = table
+                .set("cellHeight", self.cell_height)
+                .map_err(|e| errors::handle(&format!("{}{}", errors::LUA, e)));
             let _ = lua
                 .globals()
                 .set("terminal", table)
@@ -45,3 +80,19 @@ impl Inject for Terminal {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn works_without_a_terminal() {
+        let terminal = Terminal::from_dimensions(None, (0, 0));
+        assert_eq!((terminal.width, terminal.height), (0, 0));
+        assert_eq!((terminal.cell_width, terminal.cell_height), (0, 0));
+
+        let terminal = Terminal::from_dimensions(Some((120, 40)), (9, 18));
+        assert_eq!((terminal.width, terminal.height), (120, 40));
+        assert_eq!((terminal.cell_width, terminal.cell_height), (9, 18));
+    }
+}
@@ -0,0 +1,128 @@
+use crate::{assets, color, errors};
+use assets::presets::{PresetEntry, PRESETS};
+
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+/// The scripts picked with `--preset`. Each one that's set is used instead
+/// of the config dir's, and the ones that aren't work as usual.
+pub(crate) struct Preset {
+    pub info: Option<String>,
+    pub layout: Option<String>,
+    pub art: Option<String>,
+}
+
+impl Preset {
+    /// Finds a preset by name. User presets come first, so they can replace
+    /// the built-in ones.
+    pub fn find(name: &str) -> Option<Self> {
+        let path = dir().join(name);
+        if path.is_dir() {
+            return Some(Self::from_dir(&path));
+        }
+        PRESETS
+            .iter()
+            .find(|entry| entry.name == name)
+            .map(Self::from)
+    }
+
+    /// Reads a user preset: a folder in `~/.config/freshfetch/presets` with
+    /// any of `info.lua`, `layout.lua` and `art.lua` in it.
+    fn from_dir(path: &Path) -> Self {
+        Preset {
+            info: read(&path.join("info.lua")),
+            layout: read(&path.join("layout.lua")),
+            art: read(&path.join("art.lua")),
+        }
+    }
+}
+
+impl From<&PresetEntry> for Preset {
+    fn from(entry: &PresetEntry) -> Self {
+        Preset {
+            info: entry.info.map(String::from),
+            layout: entry.layout.map(String::from),
+            art: None,
+        }
+    }
+}
+
+/// Where user presets go.
+fn dir() -> PathBuf {
+    Path::new("/home/")
+        .join(env::var("USER").unwrap_or_default())
+        .join(".config/freshfetch/presets")
+}
+
+/// Reads `path` if it exists.
+fn read(path: &Path) -> Option<String> {
+    if !path.exists() {
+        return None;
+    }
+    match fs::read_to_string(path) {
+        Ok(v) => Some(v),
+        Err(e) => {
+            errors::handle(&format!(
+                "{}{}{}{}",
+                errors::io::READ.0,
+                path.to_string_lossy(),
+                errors::io::READ.1,
+                e
+            ));
+            panic!()
+        }
+    }
+}
+
+/// A user preset's description: the first line of its `layout.lua` or
+/// `info.lua`, if that's a comment.
+fn description(path: &Path) -> Option<String> {
+    ["layout.lua", "info.lua"].iter().find_map(|name| {
+        let contents = fs::read_to_string(path.join(name)).ok()?;
+        let first = contents.lines().next()?.strip_prefix("--")?.trim();
+        (!first.is_empty()).then(|| first.to_string())
+    })
+}
+
+/// Prints every built-in and user preset with its description.
+pub(crate) fn list() {
+    let mut user = fs::read_dir(dir())
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| path.is_dir())
+                .collect::<Vec<PathBuf>>()
+        })
+        .unwrap_or_default();
+    user.sort();
+    let user_names = user
+        .iter()
+        .filter_map(|path| path.file_name())
+        .map(|name| name.to_string_lossy().into_owned())
+        .collect::<Vec<String>>();
+
+    let bold = |name: &str| color::filter(&format!("\u{001b}[1m{}\u{001b}[0m", name));
+    for entry in PRESETS {
+        if user_names.iter().any(|user| user == entry.name) {
+            println!(
+                "{} - {} (replaced by yours)",
+                bold(entry.name),
+                entry.description
+            );
+        } else {
+            println!("{} - {}", bold(entry.name), entry.description);
+        }
+    }
+    for (path, user) in user.iter().zip(user_names.iter()) {
+        match description(path) {
+            Some(description) => println!(
+                "{} - {} ({})",
+                bold(user),
+                description,
+                path.to_string_lossy()
+            ),
+            None => println!("{} ({})", bold(user), path.to_string_lossy()),
+        }
+    }
+}
@@ -0,0 +1,324 @@
+use crate::unicode_width;
+
+use unicode_width::UnicodeWidthChar;
+
+const ESC: char = '\u{001b}';
+const BEL: char = '\u{0007}';
+/// The 8-bit form of `ESC [`.
+const CSI: char = '\u{009b}';
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum State {
+    Text,
+    Escape,
+    /// `ESC [`, ended by a byte in `@`..=`~`.
+    Csi,
+    /// `ESC ]`, ended by BEL or `ESC \`.
+    Osc,
+    /// `ESC P`, `ESC X`, `ESC ^` and `ESC _`, ended by `ESC \`.
+    String,
+    /// Seen `ESC` inside an OSC or string, so a `\` ends it.
+    StringEscape,
+    /// `ESC` followed by intermediate bytes, like `ESC ( B`.
+    Intermediate,
+}
+
+/// A piece of a string: a whole escape sequence or one character of text.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Piece<'a> {
+    Escape(&'a str),
+    Char(char),
+}
+
+/// Splits `s` into escape sequences and characters: CSI (colors, 24-bit
+/// SGR, cursor movement), OSC (titles, hyperlinks), DCS/APC strings (sixel,
+/// kitty graphics) and plain two character escapes.
+fn pieces(s: &str) -> Vec<Piece<'_>> {
+    let mut to_return = Vec::new();
+    let mut state = State::Text;
+    let mut start = 0;
+
+    for (i, c) in s.char_indices() {
+        let next = match state {
+            State::Text => match c {
+                ESC => State::Escape,
+                CSI => State::Csi,
+                _ => {
+                    to_return.push(Piece::Char(c));
+                    State::Text
+                }
+            },
+            State::Escape => match c {
+                '[' => State::Csi,
+                ']' => State::Osc,
+                'P' | 'X' | '^' | '_' => State::String,
+                ' '..='/' => State::Intermediate,
+                _ => State::Text,
+            },
+            State::Csi => match c {
+                '@'..='~' => State::Text,
+                _ => State::Csi,
+            },
+            State::Osc => match c {
+                BEL => State::Text,
+                ESC => State::StringEscape,
+                _ => State::Osc,
+            },
+            State::String => match c {
+                ESC => State::StringEscape,
+                _ => State::String,
+            },
+            // Anything but `\` after an `ESC` is malformed, so just keep
+            // skipping until a proper terminator.
+            State::StringEscape => match c {
+                '\\' => State::Text,
+                ESC => State::StringEscape,
+                _ => State::String,
+            },
+            State::Intermediate => match c {
+                ' '..='/' => State::Intermediate,
+                _ => State::Text,
+            },
+        };
+        if state == State::Text && next != State::Text {
+            start = i;
+        } else if state != State::Text && next == State::Text {
+            to_return.push(Piece::Escape(&s[start..i + c.len_utf8()]));
+        }
+        state = next;
+    }
+    // Keep a sequence that was cut off, so nothing goes missing.
+    if state != State::Text {
+        to_return.push(Piece::Escape(&s[start..]));
+    }
+
+    to_return
+}
+
+/// Removes escape sequences from `s`. See `pieces` for the ones that are
+/// understood.
+pub fn strip_ansi(s: &str) -> String {
+    pieces(s)
+        .into_iter()
+        .filter_map(|piece| match piece {
+            Piece::Char(c) => Some(c),
+            Piece::Escape(_) => None,
+        })
+        .collect()
+}
+
+/// The number of columns `s` takes up in a terminal. Escape sequences are
+/// ignored, East Asian wide characters count as two columns, and control
+/// and zero-width characters as none. `s` is expected to be a single line.
+pub fn display_width(s: &str) -> usize {
+    strip_ansi(s)
+        .chars()
+        .map(|c| UnicodeWidthChar::width(c).unwrap_or(0))
+        .sum()
+}
+
+/// The width of the widest line in `s`, and the number of lines.
+pub fn measure(s: &str) -> (usize, usize) {
+    let plaintext = strip_ansi(s);
+    let mut w = 0;
+    let mut h = 0;
+    for line in plaintext.lines() {
+        w = w.max(display_width(line));
+        h += 1;
+    }
+    (w, h)
+}
+
+/// Splits `s` into lines, starting each with the SGR sequences still in
+/// effect from the lines before it and ending it with a reset, so lines
+/// from different blocks of text can be printed next to each other.
+pub fn carry_sgr(s: &str) -> Vec<String> {
+    let mut active = String::new();
+    let mut to_return = Vec::new();
+    for line in s.lines() {
+        to_return.push(format!("{}{}\u{001b}[0m", active, line));
+
+        let mut rest = line;
+        while let Some(start) = rest.find("\u{001b}[") {
+            let after = &rest[start..];
+            let Some(end) = after.find(|c: char| c.is_ascii_alphabetic()) else {
+                break;
+            };
+            let sequence = &after[..=end];
+            if sequence.ends_with('m') {
+                if sequence == "\u{001b}[0m" || sequence == "\u{001b}[m" {
+                    active.clear();
+                } else {
+                    active += sequence;
+                }
+            }
+            rest = &after[end + 1..];
+        }
+    }
+    to_return
+}
+
+/// Cuts `s` down to `width` columns, ending it with `…` if anything was cut.
+/// Escape sequences are never split, and a cut line ends with a reset so
+/// its colors don't run into what comes after it.
+pub fn truncate(s: &str, width: usize) -> String {
+    if display_width(s) <= width {
+        return s.to_string();
+    }
+
+    let limit = width.saturating_sub(1);
+    let mut to_return = String::new();
+    let mut used = 0;
+    for piece in pieces(s) {
+        match piece {
+            Piece::Escape(e) => to_return += e,
+            Piece::Char(c) => {
+                let w = UnicodeWidthChar::width(c).unwrap_or(0);
+                if used + w > limit {
+                    break;
+                }
+                used += w;
+                to_return.push(c);
+            }
+        }
+    }
+    if width > 0 {
+        to_return.push('…');
+    }
+    to_return + "\u{001b}[0m"
+}
+
+/// Breaks `s` into lines of at most `width` columns, at spaces where it can.
+/// Escape sequences are never split, and colors carry over onto the
+/// following lines like `carry_sgr`.
+pub fn wrap(s: &str, width: usize) -> Vec<String> {
+    if width == 0 || display_width(s) <= width {
+        return vec![s.to_string()];
+    }
+
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    let mut used = 0;
+    // Where the last space on `line` is, and the width up to it.
+    let mut space: Option<(usize, usize)> = None;
+    for piece in pieces(s) {
+        let c = match piece {
+            Piece::Escape(e) => {
+                line += e;
+                continue;
+            }
+            Piece::Char(c) => c,
+        };
+        let w = UnicodeWidthChar::width(c).unwrap_or(0);
+        if used + w > width && used > 0 {
+            if c == ' ' {
+                // The line ends right before this space, so break there.
+                lines.push(std::mem::take(&mut line));
+                used = 0;
+                space = None;
+                continue;
+            }
+            match space.take() {
+                Some((at, before)) => {
+                    let rest = line.split_off(at);
+                    lines.push(line);
+                    // Drop the space the line was broken at.
+                    line = rest[1..].to_string();
+                    used -= before + 1;
+                }
+                None => {
+                    lines.push(std::mem::take(&mut line));
+                    used = 0;
+                }
+            }
+        }
+        if c == ' ' {
+            space = Some((line.len(), used));
+        }
+        line.push(c);
+        used += w;
+    }
+    lines.push(line);
+
+    carry_sgr(&lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_every_kind_of_escape() {
+        let s = "\u{001b}[1;38;2;1;2;3ma\u{001b}]8;;http://x\u{0007}b\u{001b}]0;t\u{001b}\\c\
+                 \u{001b}_Ga=T;AAAA\u{001b}\\d\u{001b}Pq#0!4~\u{001b}\\e\u{001b}(Bf\u{009b}2Kg";
+        assert_eq!(strip_ansi(s), "abcdefg");
+        // A cut off escape is dropped along with the rest of the line.
+        assert_eq!(strip_ansi("a\u{001b}[38;5"), "a");
+    }
+
+    #[test]
+    fn measures_display_width() {
+        assert_eq!(display_width("\u{001b}[31mabc\u{001b}[0m"), 3);
+        assert_eq!(display_width("日本"), 4);
+        assert_eq!(display_width("e\u{301}"), 1);
+        assert_eq!(measure("ab\n\u{001b}[1m日本語\u{001b}[0m\n"), (6, 2));
+    }
+
+    #[test]
+    fn carries_colors_onto_later_lines() {
+        assert_eq!(
+            carry_sgr("\u{001b}[1ma\u{001b}[31mb\nc\u{001b}[0m\nd"),
+            vec![
+                "\u{001b}[1ma\u{001b}[31mb\u{001b}[0m",
+                "\u{001b}[1m\u{001b}[31mc\u{001b}[0m\u{001b}[0m",
+                "d\u{001b}[0m",
+            ]
+        );
+        // Other escapes aren't colors, so they aren't carried.
+        assert_eq!(carry_sgr("\u{001b}[2Ka\nb")[1], "b\u{001b}[0m");
+    }
+
+    #[test]
+    fn truncates_to_width() {
+        assert_eq!(truncate("abc", 3), "abc");
+        assert_eq!(truncate("abcdef", 4), "abc…\u{001b}[0m");
+        assert_eq!(truncate("abc", 0), "\u{001b}[0m");
+        // A wide character that doesn't fit is left out whole.
+        assert_eq!(truncate("a日本", 4), "a日…\u{001b}[0m");
+        assert_eq!(
+            truncate("\u{001b}[31mabcdef\u{001b}[0m", 3),
+            "\u{001b}[31mab…\u{001b}[0m"
+        );
+    }
+
+    #[test]
+    fn wraps_at_spaces() {
+        assert_eq!(wrap("short", 10), vec!["short"]);
+        assert_eq!(wrap("short", 0), vec!["short"]);
+        assert_eq!(
+            wrap("one two three", 7),
+            vec!["one two\u{001b}[0m", "three\u{001b}[0m"]
+        );
+        // Words too long for a line are broken wherever they run out.
+        assert_eq!(
+            wrap("abcdefgh ij", 3),
+            vec![
+                "abc\u{001b}[0m",
+                "def\u{001b}[0m",
+                "gh\u{001b}[0m",
+                "ij\u{001b}[0m"
+            ]
+        );
+        assert_eq!(wrap("日本語", 4), vec!["日本\u{001b}[0m", "語\u{001b}[0m"]);
+    }
+
+    #[test]
+    fn wrapping_keeps_colors() {
+        let lines = wrap("\u{001b}[32mgreen text\u{001b}[0m", 5);
+        assert_eq!(
+            lines.iter().map(|l| strip_ansi(l)).collect::<Vec<_>>(),
+            vec!["green", "text"]
+        );
+        assert!(lines[1].starts_with("\u{001b}[32m"));
+    }
+}